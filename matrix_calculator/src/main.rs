@@ -1,14 +1,13 @@
 use std::time::Instant;
 
-mod matrix;
-use matrix::Matrix;
+use matrix::matrix::{random_f64_matrix, Matrix};
 
 fn main() {
     let _word_size = 8;
     let _precision = 5;
     let _file_name = "result.txt".to_string();
     //let _matrix_a = Matrix::random_f64_matrix(20, 7, 12, 0.0, 1000.0);
-    let matrix_b:Matrix<f64> = matrix::random_f64_matrix(15, 15, 28, 0.0, 1000.0);
+    let matrix_b:Matrix<f64> = random_f64_matrix(15, 15, 28, 0.0, 1000.0);
     let _scalar = 22;
     let start = Instant::now(); 
 
@@ -17,12 +16,12 @@ fn main() {
     let duration = start.elapsed();
     Matrix::print_matrix(&matrix_b);
     //let _ = Matrix::write_to_file(&matrix_b, &file_name, word_size, precision);
-    print!("求上三角矩阵：\n");
+    println!("求上三角矩阵：");
     //print!("{}", _scalar);
     //Matrix::print_matrix(&matrix_b);
-    print!("=\n");
+    println!("=");
     Matrix::print_matrix(&result);
     //let _ = Matrix::write_to_file(&result, &file_name, word_size, precision);
     //println!("{}", result);
     println!("Time elapsed: {} ns = {:?}", duration.as_nanos(), duration); 
-}
\ No newline at end of file
+}
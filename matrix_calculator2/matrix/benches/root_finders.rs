@@ -0,0 +1,45 @@
+// 比较三种求根算法：Wilkinson 多项式 (x - 1)(x - 2)...(x - 20) 以及系数在 [-1, 1] 中随机的 50 次多项式
+#![allow(clippy::needless_return)]
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use matrix::equation::{RootFinder, SolveOptions};
+use matrix::{Complex, Polynomial};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const METHODS: [RootFinder; 3] = [RootFinder::DurandKerner, RootFinder::AberthEhrlich, RootFinder::JenkinsTraub];
+
+fn wilkinson(degree: usize) -> Polynomial {
+    let mut result = Polynomial::from_f64(&[1.0]);
+    for k in 1..=degree {
+        result = &result * &Polynomial::from_f64(&[-(k as f64), 1.0]);
+    }
+    return result;
+}
+
+fn random_polynomial(degree: usize, seed: u64) -> Polynomial {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut coefficients: Vec<Complex> = (0..degree).map(|_| Complex::init(rng.gen_range(-1.0..1.0), 0.0)).collect();
+    coefficients.push(Complex::init(1.0, 0.0));
+    return Polynomial::new(coefficients);
+}
+
+fn bench_polynomial(criterion: &mut Criterion, name: &str, polynomial: &Polynomial) {
+    let mut group = criterion.benchmark_group(name);
+    group.sample_size(10);
+    for method in METHODS {
+        let options = SolveOptions { method, ..SolveOptions::default() };
+        group.bench_with_input(BenchmarkId::from_parameter(format!("{:?}", method)), &options, |bencher, options| {
+            bencher.iter(|| polynomial.solve_with_options(options, None));
+        });
+    }
+    group.finish();
+}
+
+fn root_finders(criterion: &mut Criterion) {
+    bench_polynomial(criterion, "wilkinson_20", &wilkinson(20));
+    bench_polynomial(criterion, "random_50", &random_polynomial(50, 7));
+}
+
+criterion_group!(benches, root_finders);
+criterion_main!(benches);
@@ -0,0 +1,439 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+use bigdecimal::ToPrimitive;
+use num_traits::One;
+use crate::equation::SolveOptions;
+use crate::expression::{Interpreter, Value};
+use crate::matrix::Matrix;
+#[cfg(feature = "rand")]
+use crate::matrix::{random_f64_matrix, random_i64_matrix};
+use crate::matrix_format::{MatrixFormatter, OutputStyle};
+use crate::matrix_io::{CsvOptions, MarketField, MarketFormat};
+use crate::matrix_parser::ParseMatrixError;
+use crate::polynomial::Polynomial;
+
+// 退出码：0 成功，其余按错误类别区分，方便脚本判断
+pub const EXIT_USAGE: i32 = 1;
+pub const EXIT_PARSE: i32 = 2;
+pub const EXIT_DIMENSION: i32 = 3;
+pub const EXIT_SINGULAR: i32 = 4;
+pub const EXIT_IO: i32 = 5;
+pub const EXIT_NO_CONVERGENCE: i32 = 6;
+
+const USAGE: &str = "\
+用法: matrix_calculator <命令> [文件...] [选项]
+不带参数时进入交互模式。文件为 - 或省略时从标准输入读取。
+
+命令：
+  det [文件]              行列式
+  inv [文件]              逆矩阵
+  mul <文件A> <文件B>      矩阵乘法 A * B
+  solve <文件A> <文件B>    求解 A X = B
+  rref [文件]             行最简形
+  eig [文件]              特征值，每行输出 (实部, 虚部)
+  roots [多项式]          多项式的根，如 \"2x^3+6x^2-2x-1\" 或 \"x² = 2\"，每行输出 (实部, 虚部)
+  random <行数> <列数>     随机矩阵
+
+选项：
+  --input auto|csv|mm|literal                       输入格式，默认 auto
+  --output plain|latex|markdown|html|csv|mm|literal  输出格式，默认 plain
+  --delimiter 字符   CSV 分隔符，默认 ,
+  --header          CSV 带表头
+  --precision N     小数位数
+  -o, --out 文件     写入文件而不是标准输出
+  --trace           det 与 inv 先输出逐步的消元过程（plain 或 latex）
+  --seed N --min X --max Y --integer   random 的种子、范围与整数模式
+
+退出码: 1 用法错误, 2 解析错误, 3 大小不匹配, 4 矩阵奇异, 5 读写失败, 6 迭代不收敛";
+
+#[derive(Debug)]
+pub enum CliError {
+    Usage(String),
+    Parse(String),
+    DimensionMismatch { operation: String, lhs: (usize, usize), rhs: (usize, usize) },
+    NotSquare { operation: String, rows: usize, cols: usize },
+    SingularMatrix,
+    Io(io::Error),
+    NoConvergence,
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => EXIT_USAGE,
+            CliError::Parse(_) => EXIT_PARSE,
+            CliError::DimensionMismatch { .. } | CliError::NotSquare { .. } => EXIT_DIMENSION,
+            CliError::SingularMatrix => EXIT_SINGULAR,
+            CliError::Io(_) => EXIT_IO,
+            CliError::NoConvergence => EXIT_NO_CONVERGENCE,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}\n\n{}", message, USAGE),
+            CliError::Parse(message) => write!(f, "解析失败: {}", message),
+            CliError::DimensionMismatch { operation, lhs, rhs } =>
+                write!(f, "{} 的矩阵大小不匹配: {}x{} 与 {}x{}", operation, lhs.0, lhs.1, rhs.0, rhs.1),
+            CliError::NotSquare { operation, rows, cols } => write!(f, "{} 需要方阵，实际为 {}x{}", operation, rows, cols),
+            CliError::SingularMatrix => write!(f, "矩阵奇异"),
+            CliError::Io(error) => write!(f, "读写失败: {}", error),
+            CliError::NoConvergence => write!(f, "迭代不收敛"),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError::Io(error)
+    }
+}
+
+impl From<ParseMatrixError> for CliError {
+    fn from(error: ParseMatrixError) -> Self {
+        match error {
+            ParseMatrixError::Io(error) => CliError::Io(error),
+            other => CliError::Parse(other.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InputFormat {
+    Auto,
+    Csv,
+    Market,
+    Literal,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OutputFormat {
+    Styled(OutputStyle),
+    Csv,
+    Market,
+    Literal,
+}
+
+struct Options {
+    command: String,
+    positional: Vec<String>,
+    input: InputFormat,
+    output: OutputFormat,
+    csv: CsvOptions,
+    precision: Option<usize>,
+    out: Option<String>,
+    seed: i64,
+    min: f64,
+    max: f64,
+    integer: bool,
+    trace: bool,
+    help: bool,
+}
+
+// 供 main 调用：执行命令，出错时把信息写到标准错误并返回退出码
+pub fn main(args: &[String]) -> i32 {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    return match run(args, &mut stdin.lock(), &mut stdout.lock()) {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("错误: {}", error);
+            error.exit_code()
+        }
+    };
+}
+
+pub fn run(args: &[String], stdin: &mut dyn BufRead, stdout: &mut dyn Write) -> Result<(), CliError> {
+    let options = parse_arguments(args)?;
+    // 帮助是正常输出，写到标准输出并正常退出
+    if options.help {
+        writeln!(stdout, "{}", USAGE)?;
+        return Ok(());
+    }
+    match &options.out {
+        Some(file_name) => {
+            let mut writer = BufWriter::new(File::create(file_name)?);
+            execute(&options, stdin, &mut writer)?;
+            writer.flush()?;
+        }
+        None => execute(&options, stdin, stdout)?,
+    }
+    return Ok(());
+}
+
+fn parse_arguments(args: &[String]) -> Result<Options, CliError> {
+    let command = args.first().ok_or(CliError::Usage("缺少命令".to_string()))?.clone();
+    let mut options = Options {
+        command,
+        positional: Vec::new(),
+        input: InputFormat::Auto,
+        output: OutputFormat::Styled(OutputStyle::Plain),
+        csv: CsvOptions::default(),
+        precision: None,
+        out: None,
+        seed: 0,
+        min: 0.0,
+        max: 1.0,
+        integer: false,
+        trace: false,
+        help: false,
+    };
+    options.help = matches!(options.command.as_str(), "-h" | "--help");
+    let mut rest = args[1..].iter();
+    while let Some(argument) = rest.next() {
+        let mut value = |name: &str| rest.next().cloned().ok_or(CliError::Usage(format!("{} 缺少参数值", name)));
+        match argument.as_str() {
+            "--input" => {
+                options.input = match value("--input")?.as_str() {
+                    "auto" => InputFormat::Auto,
+                    "csv" => InputFormat::Csv,
+                    "mm" | "market" => InputFormat::Market,
+                    "literal" => InputFormat::Literal,
+                    other => return Err(CliError::Usage(format!("未知的输入格式 {}", other))),
+                }
+            }
+            "--output" => {
+                options.output = match value("--output")?.as_str() {
+                    "plain" => OutputFormat::Styled(OutputStyle::Plain),
+                    "latex" => OutputFormat::Styled(OutputStyle::Latex),
+                    "markdown" | "md" => OutputFormat::Styled(OutputStyle::Markdown),
+                    "html" => OutputFormat::Styled(OutputStyle::Html),
+                    "csv" => OutputFormat::Csv,
+                    "mm" | "market" => OutputFormat::Market,
+                    "literal" => OutputFormat::Literal,
+                    other => return Err(CliError::Usage(format!("未知的输出格式 {}", other))),
+                }
+            }
+            "--delimiter" => {
+                let delimiter = value("--delimiter")?;
+                let mut characters = delimiter.chars();
+                options.csv.delimiter = match (characters.next(), characters.next()) {
+                    (Some(character), None) => character,
+                    _ => return Err(CliError::Usage(format!("分隔符必须是单个字符: {}", delimiter))),
+                };
+            }
+            "--header" => options.csv.has_header = true,
+            "--integer" => options.integer = true,
+            "--trace" => options.trace = true,
+            "--precision" => options.precision = Some(parse_number(&value("--precision")?)?),
+            "-o" | "--out" => options.out = Some(value("--out")?),
+            "--seed" => options.seed = parse_number(&value("--seed")?)?,
+            "--min" => options.min = parse_number(&value("--min")?)?,
+            "--max" => options.max = parse_number(&value("--max")?)?,
+            "-h" | "--help" => options.help = true,
+            other if other.starts_with("--") => return Err(CliError::Usage(format!("未知选项 {}", other))),
+            other => options.positional.push(other.to_string()),
+        }
+    }
+    return Ok(options);
+}
+
+fn parse_number<N: FromStr>(text: &str) -> Result<N, CliError> {
+    return text.parse::<N>().map_err(|_| CliError::Usage(format!("无效的数值 {}", text)));
+}
+
+fn execute(options: &Options, stdin: &mut dyn BufRead, writer: &mut dyn Write) -> Result<(), CliError> {
+    match options.command.as_str() {
+        "det" => {
+            let matrix = single_input(options, stdin)?;
+            require_square("det", &matrix)?;
+            if options.trace {
+                let (_, _, trace) = Matrix::get_upper_triangular_matrix_traced(&matrix);
+                write!(writer, "{}", trace.render(&trace_formatter(options)))?;
+            }
+            write_scalar(options, Matrix::lu(&matrix).determinant(), writer)?;
+        }
+        "inv" => {
+            let matrix = single_input(options, stdin)?;
+            require_square("inv", &matrix)?;
+            let inverse = if options.trace {
+                let (inverse, trace) = Matrix::inverse_traced(&matrix);
+                write!(writer, "{}", trace.render(&trace_formatter(options)))?;
+                inverse
+            } else {
+                Matrix::solve(&matrix, &Matrix::identity(matrix.get_rows()))
+            };
+            write_matrix(options, &inverse.ok_or(CliError::SingularMatrix)?, writer)?;
+        }
+        "mul" => {
+            let (lhs, rhs) = pair_input(options, stdin)?;
+            if lhs.get_cols() != rhs.get_rows() {
+                return Err(dimension_mismatch("mul", &lhs, &rhs));
+            }
+            #[cfg(feature = "parallel")]
+            let product = Matrix::par_multiply(&lhs, &rhs);
+            #[cfg(not(feature = "parallel"))]
+            let product = &lhs * &rhs;
+            write_matrix(options, &product, writer)?;
+        }
+        "solve" => {
+            let (lhs, rhs) = pair_input(options, stdin)?;
+            require_square("solve", &lhs)?;
+            if lhs.get_rows() != rhs.get_rows() {
+                return Err(dimension_mismatch("solve", &lhs, &rhs));
+            }
+            let solution = Matrix::solve(&lhs, &rhs).ok_or(CliError::SingularMatrix)?;
+            write_matrix(options, &solution, writer)?;
+        }
+        "rref" => {
+            let matrix = single_input(options, stdin)?;
+            write_matrix(options, &Matrix::rref(&matrix), writer)?;
+        }
+        "eig" => {
+            let matrix = single_input(options, stdin)?;
+            require_square("eig", &matrix)?;
+            let eigenvalues = Matrix::eigenvalues(&matrix).ok_or(CliError::NoConvergence)?;
+            write_matrix(options, &pairs_to_matrix(&eigenvalues), writer)?;
+        }
+        "roots" => {
+            let source = match options.positional.as_slice() {
+                [] => read_all(stdin)?,
+                [source] if source == "-" => read_all(stdin)?,
+                [source] => source.clone(),
+                _ => return Err(CliError::Usage("roots 只接受一个多项式".to_string())),
+            };
+            let roots = polynomial_roots(&source)?;
+            write_matrix(options, &pairs_to_matrix(&roots), writer)?;
+        }
+        #[cfg(feature = "rand")]
+        "random" => {
+            let (rows, cols) = match options.positional.as_slice() {
+                [rows, cols] => (parse_number::<usize>(rows)?, parse_number::<usize>(cols)?),
+                _ => return Err(CliError::Usage("random 需要行数与列数".to_string())),
+            };
+            if rows == 0 || cols == 0 {
+                return Err(CliError::Usage("行数与列数必须大于 0".to_string()));
+            }
+            if options.integer {
+                let matrix = random_i64_matrix(rows, cols, options.seed, options.min as i64, options.max as i64);
+                write_matrix(options, &matrix, writer)?;
+            } else {
+                write_matrix(options, &random_f64_matrix(rows, cols, options.seed, options.min, options.max), writer)?;
+            }
+        }
+        other => return Err(CliError::Usage(format!("未知命令 {}", other))),
+    }
+    return Ok(());
+}
+
+fn single_input(options: &Options, stdin: &mut dyn BufRead) -> Result<Matrix<f64>, CliError> {
+    return match options.positional.as_slice() {
+        [] => read_matrix(options, "-", stdin),
+        [source] => read_matrix(options, source, stdin),
+        _ => Err(CliError::Usage(format!("{} 只接受一个输入", options.command))),
+    };
+}
+
+fn pair_input(options: &Options, stdin: &mut dyn BufRead) -> Result<(Matrix<f64>, Matrix<f64>), CliError> {
+    return match options.positional.as_slice() {
+        [lhs, rhs] if lhs == "-" && rhs == "-" => Err(CliError::Usage("只能有一个输入来自标准输入".to_string())),
+        [lhs, rhs] => Ok((read_matrix(options, lhs, stdin)?, read_matrix(options, rhs, stdin)?)),
+        _ => Err(CliError::Usage(format!("{} 需要两个输入", options.command))),
+    };
+}
+
+fn read_all(stdin: &mut dyn BufRead) -> Result<String, CliError> {
+    let mut text = String::new();
+    stdin.read_to_string(&mut text)?;
+    return Ok(text);
+}
+
+fn read_matrix(options: &Options, source: &str, stdin: &mut dyn BufRead) -> Result<Matrix<f64>, CliError> {
+    let text = if source == "-" { read_all(stdin)? } else { fs::read_to_string(source)? };
+    let format = match options.input {
+        InputFormat::Auto if text.trim_start().starts_with("%%MatrixMarket") => InputFormat::Market,
+        InputFormat::Auto if options.csv.has_header || options.csv.delimiter != ',' => InputFormat::Csv,
+        InputFormat::Auto => InputFormat::Literal,
+        format => format,
+    };
+    let matrix = match format {
+        InputFormat::Csv => Matrix::read_csv(text.as_bytes(), &options.csv)?,
+        InputFormat::Market => Matrix::read_matrix_market(text.as_bytes())?,
+        _ => Matrix::from_str(&text)?,
+    };
+    return Ok(matrix);
+}
+
+fn require_square(operation: &str, matrix: &Matrix<f64>) -> Result<(), CliError> {
+    if matrix.get_rows() != matrix.get_cols() {
+        return Err(CliError::NotSquare { operation: operation.to_string(), rows: matrix.get_rows(), cols: matrix.get_cols() });
+    }
+    return Ok(());
+}
+
+fn dimension_mismatch(operation: &str, lhs: &Matrix<f64>, rhs: &Matrix<f64>) -> CliError {
+    return CliError::DimensionMismatch {
+        operation: operation.to_string(),
+        lhs: (lhs.get_rows(), lhs.get_cols()),
+        rhs: (rhs.get_rows(), rhs.get_cols()),
+    };
+}
+
+// 先按多项式字面量解析（支持上标和等号），不行再交给表达式语言，如 (x-1)^3
+fn polynomial_roots(source: &str) -> Result<Vec<(f64, f64)>, CliError> {
+    let value = match Polynomial::from_str(source) {
+        Ok(polynomial) => Some(Value::Polynomial(polynomial)),
+        Err(_) => Interpreter::new().run(source.trim()).map_err(|error| CliError::Parse(error.render(source.trim())))?,
+    };
+    let polynomial = match value {
+        Some(Value::Polynomial(polynomial)) if polynomial.degree() >= 1 => polynomial,
+        _ => return Err(CliError::Parse(format!("{} 不是次数至少为 1 的多项式", source.trim()))),
+    };
+    let (roots, report) = polynomial.solve_with_options(&SolveOptions::default(), None);
+    if !report.converged {
+        return Err(CliError::NoConvergence);
+    }
+    return Ok(roots.iter()
+        .map(|root| (root.real.to_f64().unwrap_or(f64::NAN), root.imaginary.to_f64().unwrap_or(f64::NAN)))
+        .collect());
+}
+
+// 复数结果按 (实部, 虚部) 排成两列
+fn pairs_to_matrix(pairs: &[(f64, f64)]) -> Matrix<f64> {
+    let data: Vec<f64> = pairs.iter().flat_map(|(real, imaginary)| [*real, *imaginary]).collect();
+    return Matrix::init(pairs.len(), 2, data);
+}
+
+// 消元过程只有 LaTeX 和方框两种排版
+fn trace_formatter(options: &Options) -> MatrixFormatter {
+    let style = match options.output {
+        OutputFormat::Styled(OutputStyle::Latex) => OutputStyle::Latex,
+        _ => OutputStyle::Plain,
+    };
+    return MatrixFormatter { style, precision: options.precision, ..MatrixFormatter::default() };
+}
+
+fn write_scalar(options: &Options, value: f64, writer: &mut dyn Write) -> io::Result<()> {
+    return match options.precision {
+        Some(precision) => writeln!(writer, "{:.*}", precision, value),
+        None => writeln!(writer, "{}", value),
+    };
+}
+
+fn write_matrix<T>(options: &Options, matrix: &Matrix<T>, mut writer: &mut dyn Write) -> io::Result<()> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display + FromStr + MarketField {
+    match options.output {
+        OutputFormat::Styled(style) => {
+            let formatter = MatrixFormatter { style, precision: options.precision, ..MatrixFormatter::default() };
+            formatter.write(matrix, &mut writer)?;
+        }
+        OutputFormat::Csv => Matrix::write_csv(matrix, &mut writer, &options.csv)?,
+        OutputFormat::Market => Matrix::write_matrix_market(matrix, &mut writer, MarketFormat::Array)?,
+        OutputFormat::Literal => {
+            let cell = |row: usize, col: usize| match options.precision {
+                Some(precision) => format!("{:.*}", precision, matrix.get(row, col)),
+                None => format!("{}", matrix.get(row, col)),
+            };
+            let rows: Vec<String> = (0..matrix.get_rows())
+                .map(|row| (0..matrix.get_cols()).map(|col| cell(row, col)).collect::<Vec<String>>().join(" "))
+                .collect();
+            writeln!(writer, "[{}]", rows.join("; "))?;
+        }
+    }
+    return Ok(());
+}
@@ -0,0 +1,361 @@
+use std::fmt;
+use std::rc::Rc;
+use bigdecimal::BigDecimal;
+use num_traits::{One, Signed, Zero};
+use crate::complex::Complex;
+use crate::polynomial::{decimal, Polynomial};
+
+// 一到四次方程的求根公式：二次用判别式，三次用 Cardano 公式，四次用 Ferrari 方法。
+// 根先构造成根式表达式，构造时同时算出复数值，因此数值结果和输出的根式总是一致的
+
+impl Polynomial {
+    // 次数不超过 4 时用求根公式求出全部根，重根重复出现；次数更高时返回 None
+    pub fn solve_closed_form(&self) -> Option<Vec<Complex>> {
+        return Some(radicals(self)?.iter().map(|radical| radical.value.clone()).collect());
+    }
+
+    // 与 solve_closed_form 相同的公式，以根式字符串的形式返回，如 x^2 - 2 的根为 √8/2 和 -√8/2。
+    // 系数之间能精确算出的部分直接合并，√(-3) 出现在三次和四次公式的单位根 (-1 + √(-3))/2 中
+    pub fn solve_radicals(&self) -> Option<Vec<String>> {
+        return Some(radicals(self)?.iter().map(|radical| radical.to_string()).collect());
+    }
+}
+
+// 根式表达式。两边都是数时直接合并，除法和开方只在结果精确时合并；加零、乘一之类的运算直接省略
+#[derive(Clone)]
+struct Radical {
+    value: Complex,
+    expression: Rc<Expression>,
+}
+
+enum Expression {
+    Number,
+    Add(Radical, Radical),
+    Sub(Radical, Radical),
+    Mul(Radical, Radical),
+    Div(Radical, Radical),
+    Neg(Radical),
+    Sqrt(Radical),
+    Cbrt(Radical),
+}
+
+fn node(value: Complex, expression: Expression) -> Radical {
+    return Radical { value, expression: Rc::new(expression) };
+}
+
+fn number(value: &Complex) -> Radical {
+    return node(value.clone(), Expression::Number);
+}
+
+fn integer(value: i64) -> Radical {
+    return number(&Complex::from_big_decimal(&BigDecimal::from(value), &BigDecimal::zero()));
+}
+
+fn equal(lhs: &Complex, rhs: &Complex) -> bool {
+    return lhs.get_real() == rhs.get_real() && lhs.get_imaginary() == rhs.get_imaginary();
+}
+
+impl Radical {
+    fn is_number(&self) -> bool {
+        return matches!(*self.expression, Expression::Number);
+    }
+
+    fn is_number_equal_to(&self, value: i64) -> bool {
+        return self.is_number() && equal(&self.value, &Complex::from_big_decimal(&BigDecimal::from(value), &BigDecimal::zero()));
+    }
+
+    fn is_negative_real(&self) -> bool {
+        return self.is_number() && self.value.get_imaginary().is_zero() && self.value.get_real().is_negative();
+    }
+
+    // 输出时是否以负号开头，这样的式子作为右操作数时要加括号
+    fn starts_negative(&self) -> bool {
+        return match &*self.expression {
+            Expression::Number => {
+                let (real, imaginary) = (self.value.get_real(), self.value.get_imaginary());
+                if real.is_zero() { imaginary.is_negative() } else { imaginary.is_zero() && real.is_negative() }
+            }
+            Expression::Add(lhs, _) | Expression::Sub(lhs, _) => lhs.starts_negative(),
+            // 左操作数是加减时会加括号
+            Expression::Mul(lhs, _) | Expression::Div(lhs, _) => lhs.precedence() >= 2 && lhs.starts_negative(),
+            Expression::Neg(_) => true,
+            Expression::Sqrt(_) | Expression::Cbrt(_) => false,
+        };
+    }
+
+    // 加减为 1，乘除为 2，取负为 3，数和根式为 4
+    fn precedence(&self) -> u8 {
+        return match &*self.expression {
+            Expression::Number if self.is_negative_real() => 3,
+            Expression::Add(_, _) | Expression::Sub(_, _) => 1,
+            Expression::Mul(_, _) | Expression::Div(_, _) => 2,
+            Expression::Neg(_) => 3,
+            _ => 4,
+        };
+    }
+
+    fn wrap(&self, precedence: u8, right: bool) -> String {
+        if self.precedence() < precedence || (right && self.starts_negative()) {
+            return format!("({})", self);
+        }
+        return self.to_string();
+    }
+}
+
+impl fmt::Display for Radical {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match &*self.expression {
+            Expression::Number => {
+                let (real, imaginary) = (self.value.get_real(), self.value.get_imaginary());
+                let magnitude = if imaginary.abs().is_one() { String::new() } else { decimal(&imaginary.abs()) };
+                if imaginary.is_zero() {
+                    write!(f, "{}", decimal(real))
+                } else if real.is_zero() {
+                    write!(f, "{}{}i", if imaginary.is_negative() { "-" } else { "" }, magnitude)
+                } else {
+                    write!(f, "({} {} {}i)", decimal(real), if imaginary.is_negative() { "-" } else { "+" }, magnitude)
+                }
+            }
+            Expression::Add(lhs, rhs) => write!(f, "{} + {}", lhs.wrap(1, false), rhs.wrap(1, true)),
+            Expression::Sub(lhs, rhs) => write!(f, "{} - {}", lhs.wrap(1, false), rhs.wrap(2, true)),
+            Expression::Mul(lhs, rhs) => write!(f, "{}·{}", lhs.wrap(2, false), rhs.wrap(2, true)),
+            Expression::Div(lhs, rhs) => write!(f, "{}/{}", lhs.wrap(2, false), rhs.wrap(3, true)),
+            Expression::Neg(operand) => write!(f, "-{}", operand.wrap(2, true)),
+            Expression::Sqrt(operand) => write!(f, "√{}", radicand(operand)),
+            Expression::Cbrt(operand) => write!(f, "∛{}", radicand(operand)),
+        };
+    }
+}
+
+// 根号下只有非负实数时不加括号
+fn radicand(operand: &Radical) -> String {
+    if operand.is_number() && operand.value.get_imaginary().is_zero() && !operand.value.get_real().is_negative() {
+        return operand.to_string();
+    }
+    return format!("({})", operand);
+}
+
+// 以负号开头的式子去掉负号之后的形式，如 -3/x 变为 3/x，用来把 a + (-b) 写成 a - b
+fn negated(operand: &Radical) -> Option<Radical> {
+    return match &*operand.expression {
+        Expression::Neg(inner) => Some(inner.clone()),
+        Expression::Number if operand.is_negative_real() => Some(neg(operand)),
+        Expression::Mul(lhs, rhs) => Some(mul(&negated(lhs)?, rhs)),
+        Expression::Div(lhs, rhs) => Some(div(&negated(lhs)?, rhs)),
+        _ => None,
+    };
+}
+
+fn add(lhs: &Radical, rhs: &Radical) -> Radical {
+    if let Some(positive) = negated(rhs) {
+        return sub(lhs, &positive);
+    }
+    if lhs.is_number() && rhs.is_number() {
+        return number(&(&lhs.value + &rhs.value));
+    }
+    if lhs.is_number_equal_to(0) {
+        return rhs.clone();
+    }
+    if rhs.is_number_equal_to(0) {
+        return lhs.clone();
+    }
+    return node(&lhs.value + &rhs.value, Expression::Add(lhs.clone(), rhs.clone()));
+}
+
+fn sub(lhs: &Radical, rhs: &Radical) -> Radical {
+    if let Some(positive) = negated(rhs) {
+        return add(lhs, &positive);
+    }
+    if lhs.is_number() && rhs.is_number() {
+        return number(&(&lhs.value - &rhs.value));
+    }
+    if rhs.is_number_equal_to(0) {
+        return lhs.clone();
+    }
+    if lhs.is_number_equal_to(0) {
+        return neg(rhs);
+    }
+    return node(&lhs.value - &rhs.value, Expression::Sub(lhs.clone(), rhs.clone()));
+}
+
+fn mul(lhs: &Radical, rhs: &Radical) -> Radical {
+    if (lhs.is_number() && rhs.is_number()) || lhs.is_number_equal_to(0) || rhs.is_number_equal_to(0) {
+        return number(&(&lhs.value * &rhs.value));
+    }
+    if lhs.is_number_equal_to(1) {
+        return rhs.clone();
+    }
+    if rhs.is_number_equal_to(1) {
+        return lhs.clone();
+    }
+    if lhs.is_number_equal_to(-1) {
+        return neg(rhs);
+    }
+    if rhs.is_number_equal_to(-1) {
+        return neg(lhs);
+    }
+    return node(&lhs.value * &rhs.value, Expression::Mul(lhs.clone(), rhs.clone()));
+}
+
+fn div(lhs: &Radical, rhs: &Radical) -> Radical {
+    if rhs.is_number_equal_to(1) || lhs.is_number_equal_to(0) {
+        return lhs.clone();
+    }
+    let value = &lhs.value / &rhs.value;
+    if lhs.is_number() && rhs.is_number() && equal(&(&value * &rhs.value), &lhs.value) {
+        return number(&value);
+    }
+    return node(value, Expression::Div(lhs.clone(), rhs.clone()));
+}
+
+fn neg(operand: &Radical) -> Radical {
+    let value = &Complex::new() - &operand.value;
+    if operand.is_number() {
+        return number(&value);
+    }
+    if let Expression::Neg(inner) = &*operand.expression {
+        return inner.clone();
+    }
+    return node(value, Expression::Neg(operand.clone()));
+}
+
+fn sqrt(operand: &Radical) -> Radical {
+    let value = operand.value.sqrt();
+    if operand.is_number() && equal(&(&value * &value), &operand.value) {
+        return number(&value);
+    }
+    return node(value, Expression::Sqrt(operand.clone()));
+}
+
+fn cbrt(operand: &Radical) -> Radical {
+    let value = operand.value.cbrt();
+    if operand.is_number() && equal(&(&(&value * &value) * &value), &operand.value) {
+        return number(&value);
+    }
+    return node(value, Expression::Cbrt(operand.clone()));
+}
+
+// 连乘，用于系数的单项式如 9abc
+fn product(factors: &[&Radical]) -> Radical {
+    return factors.iter().fold(integer(1), |result, factor| mul(&result, factor));
+}
+
+// 三次单位根 ξ = (-1 + √(-3))/2 与 ξ² = (-1 - √(-3))/2
+fn unit_roots() -> [Radical; 2] {
+    let root = sqrt(&integer(-3));
+    return [div(&add(&integer(-1), &root), &integer(2)), div(&sub(&integer(-1), &root), &integer(2))];
+}
+
+// (delta1 ± √(delta1² - 4 delta0³))/2 中模较大的一个，避免相消；两个都为零时返回 None
+fn cardano_cube(delta0: &Radical, delta1: &Radical) -> Option<Radical> {
+    let root = sqrt(&sub(&mul(delta1, delta1), &product(&[&integer(4), delta0, delta0, delta0])));
+    let plus = div(&add(delta1, &root), &integer(2));
+    let minus = div(&sub(delta1, &root), &integer(2));
+    let cube = if plus.value.abs() >= minus.value.abs() { plus } else { minus };
+    if cube.value.is_zero() {
+        return None;
+    }
+    return Some(cube);
+}
+
+fn radicals(polynomial: &Polynomial) -> Option<Vec<Radical>> {
+    let coefficients: Vec<Radical> = polynomial.get_coefficients().iter().map(number).collect();
+    return match polynomial.degree() {
+        0 => Some(Vec::new()),
+        1 => Some(vec![div(&neg(&coefficients[0]), &coefficients[1])]),
+        2 => Some(quadratic(&coefficients[2], &coefficients[1], &coefficients[0])),
+        3 => Some(cubic(&coefficients[3], &coefficients[2], &coefficients[1], &coefficients[0])),
+        4 => quartic(&coefficients[4], &coefficients[3], &coefficients[2], &coefficients[1], &coefficients[0]),
+        _ => None,
+    };
+}
+
+// ax² + bx + c：(-b ± √(b² - 4ac))/(2a)
+fn quadratic(a: &Radical, b: &Radical, c: &Radical) -> Vec<Radical> {
+    let root = sqrt(&sub(&mul(b, b), &product(&[&integer(4), a, c])));
+    let denominator = mul(&integer(2), a);
+    return vec![div(&add(&neg(b), &root), &denominator), div(&sub(&neg(b), &root), &denominator)];
+}
+
+// ax³ + bx² + cx + d 的 Cardano 公式：x_k = -(b + ξ^k C + Δ0/(ξ^k C))/(3a)，
+// 其中 Δ0 = b² - 3ac，Δ1 = 2b³ - 9abc + 27a²d，C = ∛((Δ1 ± √(Δ1² - 4Δ0³))/2)
+fn cubic(a: &Radical, b: &Radical, c: &Radical, d: &Radical) -> Vec<Radical> {
+    let denominator = mul(&integer(3), a);
+    let delta0 = sub(&mul(b, b), &product(&[&integer(3), a, c]));
+    let delta1 = add(&sub(&product(&[&integer(2), b, b, b]), &product(&[&integer(9), a, b, c])), &product(&[&integer(27), a, a, d]));
+    let cube = match cardano_cube(&delta0, &delta1) {
+        Some(cube) => cube,
+        // Δ0 = Δ1 = 0 时是三重根
+        None => return vec![neg(&div(b, &denominator)); 3],
+    };
+    let big_c = cbrt(&cube);
+    let [xi, xi_squared] = unit_roots();
+    return [big_c.clone(), mul(&xi, &big_c), mul(&xi_squared, &big_c)].iter()
+        .map(|u| neg(&div(&add(&add(b, u), &div(&delta0, u)), &denominator)))
+        .collect();
+}
+
+// ax⁴ + bx³ + cx² + dx + e 的 Ferrari 方法。令 x = y - b/(4a) 得到 y⁴ + py² + qy + r，
+// q = 0 时按 y² 的二次方程求解，否则 x = -b/(4a) ∓ S ± ½√(-4S² - 2p ± q/S)，
+// S = ½√(-2p/3 + (Q + Δ0/Q)/(3a))，Q 是三次预解式的 Cardano 立方根，S = 0 时换一个立方根
+fn quartic(a: &Radical, b: &Radical, c: &Radical, d: &Radical, e: &Radical) -> Option<Vec<Radical>> {
+    let two = integer(2);
+    let shift = neg(&div(b, &mul(&integer(4), a)));
+    let p = div(&sub(&product(&[&integer(8), a, c]), &product(&[&integer(3), b, b])), &product(&[&integer(8), a, a]));
+    let q_numerator = add(&sub(&product(&[b, b, b]), &product(&[&integer(4), a, b, c])), &product(&[&integer(8), a, a, d]));
+    let q = div(&q_numerator, &product(&[&integer(8), a, a, a]));
+    let mut result: Vec<Radical> = Vec::new();
+    if q.value.is_zero() {
+        // r = (-3b⁴ + 256a³e - 64a²bd + 16ab²c)/(256a⁴)
+        let r_numerator = add(
+            &sub(&sub(&product(&[&integer(256), a, a, a, e]), &product(&[&integer(3), b, b, b, b])), &product(&[&integer(64), a, a, b, d])),
+            &product(&[&integer(16), a, b, b, c]),
+        );
+        let r = div(&r_numerator, &product(&[&integer(256), a, a, a, a]));
+        let root = sqrt(&sub(&mul(&p, &p), &mul(&integer(4), &r)));
+        for square in [div(&add(&neg(&p), &root), &two), div(&sub(&neg(&p), &root), &two)] {
+            let y = sqrt(&square);
+            result.push(add(&shift, &y));
+            result.push(sub(&shift, &y));
+        }
+        return Some(result);
+    }
+    let delta0 = add(&sub(&mul(c, c), &product(&[&integer(3), b, d])), &product(&[&integer(12), a, e]));
+    let delta1 = sub(
+        &add(
+            &add(&sub(&product(&[&two, c, c, c]), &product(&[&integer(9), b, c, d])), &product(&[&integer(27), b, b, e])),
+            &product(&[&integer(27), a, d, d]),
+        ),
+        &product(&[&integer(72), a, c, e]),
+    );
+    let base = div(&mul(&integer(-2), &p), &integer(3));
+    let candidates: Vec<Option<Radical>> = match cardano_cube(&delta0, &delta1) {
+        Some(cube) => {
+            let big_q = cbrt(&cube);
+            let [xi, xi_squared] = unit_roots();
+            vec![Some(big_q.clone()), Some(mul(&xi, &big_q)), Some(mul(&xi_squared, &big_q))]
+        }
+        None => vec![None],
+    };
+    let (inner, s) = candidates.iter()
+        .map(|big_q| {
+            let inner = match big_q {
+                Some(big_q) => add(&base, &div(&add(big_q, &div(&delta0, big_q)), &mul(&integer(3), a))),
+                None => base.clone(),
+            };
+            let s = div(&sqrt(&inner), &two);
+            (inner, s)
+        })
+        .find(|(_, s)| !s.value.is_zero())?;
+    // -4S² - 2p = -inner - 2p
+    let remaining = sub(&neg(&inner), &mul(&two, &p));
+    let ratio = div(&q, &s);
+    let left_root = div(&sqrt(&add(&remaining, &ratio)), &two);
+    let right_root = div(&sqrt(&sub(&remaining, &ratio)), &two);
+    let (left, right) = (sub(&shift, &s), add(&shift, &s));
+    result.push(add(&left, &left_root));
+    result.push(sub(&left, &left_root));
+    result.push(add(&right, &right_root));
+    result.push(sub(&right, &right_root));
+    return Some(result);
+}
@@ -1,13 +1,20 @@
 use std::fmt;
 use std::ops::{Add, Div, Mul, Sub};
-use std::ptr::read;
 use std::str::FromStr;
 use bigdecimal::BigDecimal;
-use num_traits::{FromPrimitive, Signed, ToPrimitive};
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+
+// cbrt 的 Newton 迭代保留的小数位数
+const ROOT_PRECISION: i64 = 60;
+// complex_pow 中间结果保留的有效数字位数，避免反复平方时位数成倍增长
+const POW_PRECISION: u64 = 60;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Complex {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::big_decimal_string"))]
     pub(crate) real:BigDecimal,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::big_decimal_string"))]
     pub(crate) imaginary:BigDecimal,
 }
 
@@ -26,6 +33,18 @@ impl Complex {
         }
     }
 
+    pub fn get_real(&self) -> &BigDecimal{
+        return &self.real;
+    }
+
+    pub fn get_imaginary(&self) -> &BigDecimal{
+        return &self.imaginary;
+    }
+
+    pub fn is_zero(&self) -> bool{
+        return self.real.is_zero() && self.imaginary.is_zero();
+    }
+
     pub fn from_big_decimal(_real:&BigDecimal, _imaginary:&BigDecimal) -> Complex{
         Complex{
             real: _real.clone(),
@@ -33,6 +52,11 @@ impl Complex {
         }
     }
 
+    // 实部和虚部都四舍五入到 digits 位小数
+    pub fn round(&self, digits:i64) -> Complex{
+        return Complex::from_big_decimal(&self.real.round(digits), &self.imaginary.round(digits));
+    }
+
     pub fn conjugate(target:&Complex) -> Complex{
         let mut result:Complex = Complex::new();
         result.real = target.real.clone();
@@ -40,19 +64,32 @@ impl Complex {
         return result;
     }
     
+    // 反复平方，乘法次数与指数的位数成正比；负指数按 0 处理
     pub fn complex_pow(target:&Complex, pow:i32) -> Complex{
         let mut result = Complex::init(1.0, 0.0);
-        for _ in 0..pow {
-            result = &result * target;
+        let mut square = target.clone();
+        let mut remaining = pow.max(0) as u32;
+        while remaining > 0 {
+            if remaining % 2 == 1 {
+                result = (&result * &square).with_precision(POW_PRECISION);
+            }
+            remaining /= 2;
+            if remaining > 0 {
+                square = (&square * &square).with_precision(POW_PRECISION);
+            }
         }
         return result;
     }
 
+    // 实部和虚部各保留 digits 位有效数字
+    fn with_precision(&self, digits:u64) -> Complex{
+        return Complex::from_big_decimal(&self.real.with_prec(digits), &self.imaginary.with_prec(digits));
+    }
+
     pub fn pow(target:&Complex, pow:i32) -> Complex{
         let (mut r, mut theta) = Self::to_polar(target);
         r = Self::fast_pow(&r, pow as u64);
-        theta = theta * pow;
-        print!("{}\n",Self::from_big_decimal(&r,&theta));
+        theta *= pow;
         return Self::from_polar(&r, &theta);
     }
 
@@ -64,14 +101,13 @@ impl Complex {
 
     pub fn from_polar(r:&BigDecimal, theta:&BigDecimal) -> Complex{
         let mut result:Complex = Complex::new();
-        result.real = r * &Self::calc_cos(theta, 10);
-        result.imaginary = r * &Self::calc_sin(theta, 10);
-        print!("{}\n",&result);
+        result.real = r * &Self::calc_cos(theta, 30);
+        result.imaginary = r * &Self::calc_sin(theta, 30);
         return result;
     }
 
     pub fn calc_cos(angle: &BigDecimal, terms:u64) -> BigDecimal {
-        let normalized_angle = Self::normalize_angle(&angle);
+        let normalized_angle = Self::normalize_angle(angle);
         let mut result = BigDecimal::from(0);
         let mut factorial = BigDecimal::from(1);
         for term in 0..terms {
@@ -90,8 +126,7 @@ impl Complex {
     }
 
     pub fn calc_sin(angle: &BigDecimal, terms:u64) -> BigDecimal {
-        let normalized_angle = Self::normalize_angle(&angle);
-        let terms = 30;
+        let normalized_angle = Self::normalize_angle(angle);
         let mut result = BigDecimal::from(0);
         let mut factorial = BigDecimal::from(1);
         for term in 0..terms {
@@ -111,18 +146,18 @@ impl Complex {
 
 
     fn normalize_angle(angle:&BigDecimal) -> BigDecimal{
-        let PI = BigDecimal::from_str("3.141592653589793238462643383279").unwrap();
-        let ratio = (angle / &(2 * &PI)).to_i64().unwrap();
-        let normalized_angle:BigDecimal = angle - BigDecimal::from_i64(ratio).unwrap() * (2 * &PI);
+        let pi = BigDecimal::from_str("3.141592653589793238462643383279").unwrap();
+        let ratio = (angle / &(2 * &pi)).to_i64().unwrap();
+        let normalized_angle:BigDecimal = angle - BigDecimal::from_i64(ratio).unwrap() * (2 * &pi);
         return normalized_angle;
     }
 
-    fn calc_arctan(mut num:&BigDecimal) -> BigDecimal{
+    fn calc_arctan(num:&BigDecimal) -> BigDecimal{
         let num = num.round(40);
-        let PI = BigDecimal::from_str("3.141592653589793238462643383279").unwrap();
+        let pi = BigDecimal::from_str("3.141592653589793238462643383279").unwrap();
         let terms = 1000;
         let mut result = BigDecimal::from(0);
-        if BigDecimal::abs(&num) < BigDecimal::from(1) {
+        if BigDecimal::abs(&num) < 1 {
             for term in 1..terms {
                 let n = 2 * term - 1;
                 if term % 2 == 0 {
@@ -133,15 +168,15 @@ impl Complex {
                 result = result.round(50);
             }
         }else if BigDecimal::eq(&num, &BigDecimal::from(1)) {
-            result = &PI / 4;
+            result = &pi / 4;
             return result;
         }else if BigDecimal::eq(&num, &BigDecimal::from(-1)){
-            result = -&PI / 4;
+            result = -&pi / 4;
             return result;
         }else if BigDecimal::is_negative(&num) {
-            result = -&PI / 2 - Self::calc_arctan(&(&BigDecimal::from(1) / num));
+            result = -&pi / 2 - Self::calc_arctan(&(&BigDecimal::from(1) / num));
         }else if BigDecimal::is_positive(&num) {
-            result = &PI / 2 - Self::calc_arctan(&(&BigDecimal::from(1) / num));
+            result = &pi / 2 - Self::calc_arctan(&(&BigDecimal::from(1) / num));
         }
         return result;
     }
@@ -164,14 +199,67 @@ impl Complex {
     pub fn exp(target:&Complex) -> Complex{
         let mut result = Complex::new();
         let real_exp = target.real.exp();
-        result.real = real_exp.clone() * Self::calc_cos(&target.imaginary, 5);
-        result.imaginary = real_exp.clone() * Self::calc_sin(&target.imaginary, 5);
+        result.real = real_exp.clone() * Self::calc_cos(&target.imaginary, 30);
+        result.imaginary = real_exp.clone() * Self::calc_sin(&target.imaginary, 30);
         return result;
     }
     
     pub fn abs(&self) -> BigDecimal{
         return BigDecimal::sqrt(&(&self.real * &self.real + &self.imaginary * &self.imaginary)).unwrap()  
     }
+
+    // 主平方根，实部非负；实部为零时虚部非负
+    pub fn sqrt(&self) -> Complex{
+        if self.is_zero() {
+            return Complex::new();
+        }
+        let two = BigDecimal::from(2);
+        let modulus = self.abs();
+        // |z| 是近似值，|z| ± 实部可能略小于零
+        let real = ((&modulus + &self.real) / &two).max(BigDecimal::zero()).sqrt().unwrap();
+        let imaginary = ((&modulus - &self.real) / &two).max(BigDecimal::zero()).sqrt().unwrap();
+        let imaginary = if self.imaginary.is_negative() { -imaginary } else { imaginary };
+        return Complex::from_big_decimal(&real, &imaginary);
+    }
+
+    // 主立方根，辐角在 (-π/3, π/3] 中。用 f64 的极坐标给出初值，再做 Newton 迭代 w -= (w³ - z) / 3w²。
+    // z 先按 10^(3k) 缩放到 [1, 1000) 附近再转成 f64，极大或极小的 z 也不会让初值变成 0 或无穷，
+    // 迭代保留的小数位数随 k 调整，使有效数字的位数不变
+    pub fn cbrt(&self) -> Complex{
+        if self.is_zero() {
+            return Complex::new();
+        }
+        let magnitude = [&self.real, &self.imaginary].into_iter()
+            .filter(|part| !part.is_zero())
+            .map(|part| part.order_of_magnitude())
+            .max()
+            .unwrap();
+        let shift = magnitude.div_euclid(3);
+        let scale_down = BigDecimal::new(1.into(), 3 * shift);
+        let (real, imaginary) = ((&self.real * &scale_down).to_f64().unwrap_or(0.0), (&self.imaginary * &scale_down).to_f64().unwrap_or(0.0));
+        let (radius, angle) = (real.hypot(imaginary).cbrt(), imaginary.atan2(real) / 3.0);
+        let scale_up = BigDecimal::new(1.into(), -shift);
+        let mut result = Complex::from_big_decimal(
+            &(BigDecimal::from_f64(radius * angle.cos()).unwrap() * &scale_up),
+            &(BigDecimal::from_f64(radius * angle.sin()).unwrap() * &scale_up));
+        let three = Complex::init(3.0, 0.0);
+        let precision = ROOT_PRECISION - shift;
+        for _ in 0..20 {
+            let square = &result * &result;
+            let step = (&(&(&square * &result) - self) / &(&three * &square)).round(precision);
+            result = (&result - &step).round(precision);
+            if step.is_zero() {
+                break;
+            }
+        }
+        return result;
+    }
+}
+
+impl Default for Complex {
+    fn default() -> Self {
+        Complex::new()
+    }
 }
 
 impl fmt::Display for Complex {
@@ -0,0 +1,435 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use num_traits::One;
+use crate::matrix::Matrix;
+use crate::permutation::Permutation;
+
+const MAX_QR_ITERATIONS: usize = 60;
+
+// 带部分主元的 LU 分解：P A = L U，P 为 permutation 对应的置换矩阵，L 为单位下三角
+#[derive(Clone, Debug)]
+pub struct LuDecomposition<T> {
+    pub l: Matrix<T>,
+    pub u: Matrix<T>,
+    pub permutation: Permutation,
+}
+
+// 带列主元的 Householder QR 分解：A P = Q R，A P 即 permutation.apply_to_cols(A)
+#[derive(Clone, Debug)]
+pub struct QrDecomposition {
+    pub q: Matrix<f64>,
+    pub r: Matrix<f64>,
+    pub permutation: Permutation,
+}
+
+impl<T> LuDecomposition<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    // U 的对角元之积，符号由置换的奇偶性决定
+    pub fn determinant(&self) -> T {
+        let mut result = T::one();
+        for index in 0..self.u.get_rows() {
+            result = result * *self.u.get(index, index);
+        }
+        return if self.permutation.sign() > 0 { result } else { T::default() - result };
+    }
+
+    // 先置换右端项，再依次前代、回代求解 A X = B，U 的对角元有零时返回 None
+    pub fn solve(&self, rhs: &Matrix<T>) -> Option<Matrix<T>> {
+        let size = self.u.get_rows();
+        assert_eq!(rhs.get_rows(), size, "矩阵大小不匹配");
+        let mut result = self.permutation.apply_to_rows(rhs);
+        for col in 0..rhs.get_cols() {
+            for row in 0..size {
+                let mut value = *result.get(row, col);
+                for k in 0..row {
+                    value = value - *self.l.get(row, k) * *result.get(k, col);
+                }
+                result.set(row, col, value);
+            }
+            for row in (0..size).rev() {
+                let diagonal = *self.u.get(row, row);
+                if diagonal == T::default() {
+                    return None;
+                }
+                let mut value = *result.get(row, col);
+                for k in row + 1..size {
+                    value = value - *self.u.get(row, k) * *result.get(k, col);
+                }
+                result.set(row, col, value / diagonal);
+            }
+        }
+        return Some(result);
+    }
+}
+
+impl QrDecomposition {
+    // R 的对角元按绝对值递减，绝对值大于 tolerance 的个数即为秩
+    pub fn rank(&self, tolerance: f64) -> usize {
+        let size = self.r.get_rows().min(self.r.get_cols());
+        return (0..size).take_while(|index| self.r.get(*index, *index).abs() > tolerance).count();
+    }
+
+    // 最小二乘解 min ‖A X - B‖：先算 Qᵀ B，再用 R 的前 n 行回代得到 Pᵀ X，最后按置换还原。
+    // A 不是列满秩（按 tolerance 判断）时返回 None
+    pub fn least_squares(&self, rhs: &Matrix<f64>, tolerance: f64) -> Option<Matrix<f64>> {
+        assert_eq!(rhs.get_rows(), self.q.get_rows(), "矩阵大小不匹配");
+        let cols = self.r.get_cols();
+        if self.rank(tolerance) < cols {
+            return None;
+        }
+        let projected = &Matrix::transpose(&self.q) * rhs;
+        let mut result: Matrix<f64> = Matrix::new(cols, rhs.get_cols());
+        for col in 0..rhs.get_cols() {
+            let mut solution = vec![0.0; cols];
+            for row in (0..cols).rev() {
+                let mut value = *projected.get(row, col);
+                for (k, known) in solution.iter().enumerate().skip(row + 1) {
+                    value -= self.r.get(row, k) * known;
+                }
+                solution[row] = value / self.r.get(row, row);
+            }
+            for (index, value) in solution.iter().enumerate() {
+                result.set(self.permutation.get(index), col, *value);
+            }
+        }
+        return Some(result);
+    }
+}
+
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn lu(target:&Matrix<T>) -> LuDecomposition<T>{
+        assert_eq!(target.get_rows(), target.get_cols(), "只有方阵可以做 LU 分解");
+        let size = target.get_rows();
+        let mut l: Matrix<T> = Matrix::identity(size);
+        let mut u: Matrix<T> = target.clone();
+        let mut permutation = Permutation::identity(size);
+        for col in 0..size {
+            let mut pivot_index = col;
+            for row in col + 1..size {
+                if Self::abs_of(*u.get(row, col)) > Self::abs_of(*u.get(pivot_index, col)) {
+                    pivot_index = row;
+                }
+            }
+            if pivot_index != col {
+                u.swap_rows(pivot_index, col);
+                permutation.swap(pivot_index, col);
+                // L 中已经求出的乘数随行一起交换
+                for k in 0..col {
+                    let cache = *l.get(col, k);
+                    l.set(col, k, *l.get(pivot_index, k));
+                    l.set(pivot_index, k, cache);
+                }
+            }
+            let pivot = *u.get(col, col);
+            if pivot == T::default() {
+                continue;
+            }
+            for row in col + 1..size {
+                let factor = *u.get(row, col) / pivot;
+                l.set(row, col, factor);
+                u.add_row_multiple(row, col, T::default() - factor);
+                u.set(row, col, T::default());
+            }
+        }
+        return LuDecomposition { l, u, permutation };
+    }
+}
+
+impl Matrix<f64> {
+    pub fn qr(target:&Matrix<f64>) -> QrDecomposition{
+        let (rows, cols) = (target.get_rows(), target.get_cols());
+        let mut r = target.clone();
+        let mut q: Matrix<f64> = Matrix::identity(rows);
+        let mut permutation = Permutation::identity(cols);
+        for step in 0..rows.min(cols) {
+            // 选剩余部分范数最大的列作为主元列
+            let column_norm = |matrix: &Matrix<f64>, col: usize| (step..rows).map(|row| matrix.get(row, col).powi(2)).sum::<f64>();
+            let pivot_col = (step..cols).fold(step, |best, col| if column_norm(&r, col) > column_norm(&r, best) { col } else { best });
+            if pivot_col != step {
+                r.swap_cols(pivot_col, step);
+                permutation.swap(pivot_col, step);
+            }
+            let norm = column_norm(&r, step).sqrt();
+            if norm == 0.0 {
+                break;
+            }
+            // Householder 向量 v = x - alpha e1，H = I - 2 v vᵀ / (vᵀ v)
+            let alpha = if *r.get(step, step) > 0.0 { -norm } else { norm };
+            let mut v: Vec<f64> = (step..rows).map(|row| *r.get(row, step)).collect();
+            v[0] -= alpha;
+            let v_norm_squared: f64 = v.iter().map(|value| value * value).sum();
+            if v_norm_squared == 0.0 {
+                continue;
+            }
+            for col in step..cols {
+                let dot: f64 = v.iter().enumerate().map(|(index, value)| value * r.get(step + index, col)).sum();
+                let scale = 2.0 * dot / v_norm_squared;
+                for (index, value) in v.iter().enumerate() {
+                    r.set(step + index, col, r.get(step + index, col) - scale * value);
+                }
+            }
+            for row in 0..rows {
+                let dot: f64 = v.iter().enumerate().map(|(index, value)| value * q.get(row, step + index)).sum();
+                let scale = 2.0 * dot / v_norm_squared;
+                for (index, value) in v.iter().enumerate() {
+                    q.set(row, step + index, q.get(row, step + index) - scale * value);
+                }
+            }
+            for row in step + 1..rows {
+                r.set(row, step, 0.0);
+            }
+        }
+        return QrDecomposition { q, r, permutation };
+    }
+
+    // 用对角相似变换 D^-1 A D 平衡各行各列的范数（Parlett–Reinsch），特征值不变。
+    // D 的对角元都是 2 的幂，因此变换没有舍入误差，之后求特征值的精度更高
+    pub fn balance(target:&Matrix<f64>) -> Matrix<f64>{
+        assert_eq!(target.get_rows(), target.get_cols(), "只有方阵可以平衡");
+        const RADIX: f64 = 2.0;
+        let size = target.get_rows();
+        let mut result = target.clone();
+        let mut done = false;
+        while !done {
+            done = true;
+            for i in 0..size {
+                let mut column_norm = 0.0;
+                let mut row_norm = 0.0;
+                for j in 0..size {
+                    if j != i {
+                        column_norm += result.get(j, i).abs();
+                        row_norm += result.get(i, j).abs();
+                    }
+                }
+                if column_norm == 0.0 || row_norm == 0.0 {
+                    continue;
+                }
+                let total = column_norm + row_norm;
+                let mut factor = 1.0;
+                let mut bound = row_norm / RADIX;
+                while column_norm < bound {
+                    factor *= RADIX;
+                    column_norm *= RADIX * RADIX;
+                }
+                bound = row_norm * RADIX;
+                while column_norm > bound {
+                    factor /= RADIX;
+                    column_norm /= RADIX * RADIX;
+                }
+                if (column_norm + row_norm) / factor < 0.95 * total {
+                    done = false;
+                    result.scale_row(i, 1.0 / factor);
+                    result.scale_col(i, factor);
+                }
+            }
+        }
+        return result;
+    }
+
+    // 先用带主元的相似变换化为上 Hessenberg 矩阵，再用 Francis 双步位移 QR 迭代求全部特征值。
+    // 返回 (实部, 虚部)，不收敛时返回 None
+    pub fn eigenvalues(target:&Matrix<f64>) -> Option<Vec<(f64, f64)>>{
+        assert_eq!(target.get_rows(), target.get_cols(), "只有方阵可以求特征值");
+        let size = target.get_rows();
+        let mut a: Vec<Vec<f64>> = (0..size).map(|row| (0..size).map(|col| *target.get(row, col)).collect()).collect();
+        Self::reduce_to_hessenberg(&mut a);
+        return Self::hessenberg_qr(&mut a);
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    fn reduce_to_hessenberg(a: &mut [Vec<f64>]) {
+        let size = a.len();
+        for m in 1..size.saturating_sub(1) {
+            let mut pivot = 0.0;
+            let mut pivot_index = m;
+            for (j, row) in a.iter().enumerate().skip(m) {
+                if row[m - 1].abs() > f64::abs(pivot) {
+                    pivot = row[m - 1];
+                    pivot_index = j;
+                }
+            }
+            if pivot_index != m {
+                a.swap(pivot_index, m);
+                for row in a.iter_mut() {
+                    row.swap(pivot_index, m);
+                }
+            }
+            if pivot != 0.0 {
+                for i in m + 1..size {
+                    let mut factor = a[i][m - 1];
+                    if factor != 0.0 {
+                        factor /= pivot;
+                        a[i][m - 1] = 0.0;
+                        for j in m..size {
+                            a[i][j] -= factor * a[m][j];
+                        }
+                        for row in a.iter_mut() {
+                            row[m] += factor * row[i];
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    fn hessenberg_qr(a: &mut [Vec<f64>]) -> Option<Vec<(f64, f64)>> {
+        let size = a.len() as isize;
+        let mut eigenvalues: Vec<(f64, f64)> = vec![(0.0, 0.0); a.len()];
+        let mut norm = 0.0;
+        for i in 0..size {
+            for j in (i - 1).max(0)..size {
+                norm += a[i as usize][j as usize].abs();
+            }
+        }
+        let at = |i: isize, j: isize| (i as usize, j as usize);
+        let mut nn: isize = size - 1;
+        let mut shift = 0.0;
+        while nn >= 0 {
+            let mut iterations = 0;
+            loop {
+                // 寻找可以分离的小次对角元
+                let mut l = nn;
+                while l > 0 {
+                    let (p, q) = at(l, l);
+                    let mut s = a[p - 1][q - 1].abs() + a[p][q].abs();
+                    if s == 0.0 {
+                        s = norm;
+                    }
+                    if a[p][q - 1].abs() <= f64::EPSILON * s {
+                        a[p][q - 1] = 0.0;
+                        break;
+                    }
+                    l -= 1;
+                }
+                let n = nn as usize;
+                let mut x = a[n][n];
+                if l == nn {
+                    eigenvalues[n] = (x + shift, 0.0);
+                    nn -= 1;
+                    break;
+                }
+                let mut y = a[n - 1][n - 1];
+                let mut w = a[n][n - 1] * a[n - 1][n];
+                if l == nn - 1 {
+                    let p = 0.5 * (y - x);
+                    let q = p * p + w;
+                    let mut z = q.abs().sqrt();
+                    x += shift;
+                    if q >= 0.0 {
+                        z = p + z.copysign(p);
+                        eigenvalues[n - 1] = (x + z, 0.0);
+                        eigenvalues[n] = if z != 0.0 { (x - w / z, 0.0) } else { (x + z, 0.0) };
+                    } else {
+                        eigenvalues[n] = (x + p, -z);
+                        eigenvalues[n - 1] = (x + p, z);
+                    }
+                    nn -= 2;
+                    break;
+                }
+                if iterations == MAX_QR_ITERATIONS {
+                    return None;
+                }
+                // 特殊位移，避免在某些矩阵上停滞
+                if iterations == 10 || iterations == 20 {
+                    shift += x;
+                    for (i, row) in a.iter_mut().enumerate().take(n + 1) {
+                        row[i] -= x;
+                    }
+                    let s = a[n][n - 1].abs() + a[n - 1][n - 2].abs();
+                    x = 0.75 * s;
+                    y = x;
+                    w = -0.4375 * s * s;
+                }
+                iterations += 1;
+
+                let mut m = nn - 2;
+                let (mut p, mut q, mut r);
+                loop {
+                    let k = m as usize;
+                    let z = a[k][k];
+                    let r0 = x - z;
+                    let s0 = y - z;
+                    p = (r0 * s0 - w) / a[k + 1][k] + a[k][k + 1];
+                    q = a[k + 1][k + 1] - z - r0 - s0;
+                    r = a[k + 2][k + 1];
+                    let s = p.abs() + q.abs() + r.abs();
+                    p /= s;
+                    q /= s;
+                    r /= s;
+                    if m == l {
+                        break;
+                    }
+                    let u = a[k][k - 1].abs() * (q.abs() + r.abs());
+                    let v = p.abs() * (a[k - 1][k - 1].abs() + z.abs() + a[k + 1][k + 1].abs());
+                    if u <= f64::EPSILON * v {
+                        break;
+                    }
+                    m -= 1;
+                }
+                let m = m as usize;
+                for i in m..n - 1 {
+                    a[i + 2][i] = 0.0;
+                    if i != m {
+                        a[i + 2][i - 1] = 0.0;
+                    }
+                }
+                let l = l as usize;
+                for k in m..n {
+                    if k != m {
+                        p = a[k][k - 1];
+                        q = a[k + 1][k - 1];
+                        r = if k + 1 != n { a[k + 2][k - 1] } else { 0.0 };
+                        x = p.abs() + q.abs() + r.abs();
+                        if x != 0.0 {
+                            p /= x;
+                            q /= x;
+                            r /= x;
+                        }
+                    }
+                    let s = (p * p + q * q + r * r).sqrt().copysign(p);
+                    if s == 0.0 {
+                        continue;
+                    }
+                    if k == m {
+                        if l != m {
+                            a[k][k - 1] = -a[k][k - 1];
+                        }
+                    } else {
+                        a[k][k - 1] = -s * x;
+                    }
+                    p += s;
+                    x = p / s;
+                    y = q / s;
+                    let z = r / s;
+                    q /= p;
+                    r /= p;
+                    for j in k..=n {
+                        let mut t = a[k][j] + q * a[k + 1][j];
+                        if k + 1 != n {
+                            t += r * a[k + 2][j];
+                            a[k + 2][j] -= t * z;
+                        }
+                        a[k + 1][j] -= t * y;
+                        a[k][j] -= t * x;
+                    }
+                    let upper = n.min(k + 3);
+                    for row in a.iter_mut().take(upper + 1).skip(l) {
+                        let mut t = x * row[k] + y * row[k + 1];
+                        if k + 1 != n {
+                            t += z * row[k + 2];
+                            row[k + 2] -= t * r;
+                        }
+                        row[k + 1] -= t * q;
+                        row[k] -= t;
+                    }
+                }
+                if l + 1 >= n {
+                    break;
+                }
+            }
+        }
+        return Some(eigenvalues);
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use num_traits::One;
+use crate::matrix::Matrix;
+
+// 初等变换。行变换等价于左乘一个初等矩阵，列变换等价于右乘一个初等矩阵
+#[derive(Clone, PartialEq, Debug)]
+pub enum ElementaryOperation<T> {
+    SwapRows(usize, usize),
+    // 第 row 行乘以 factor
+    ScaleRow { row: usize, factor: T },
+    // 第 target 行加上第 source 行的 factor 倍
+    AddRowMultiple { target: usize, source: usize, factor: T },
+    // 新的第 i 行为原来的第 permutation[i] 行
+    PermuteRows(Vec<usize>),
+    SwapCols(usize, usize),
+    ScaleCol { col: usize, factor: T },
+    AddColMultiple { target: usize, source: usize, factor: T },
+    // 新的第 j 列为原来的第 permutation[j] 列
+    PermuteCols(Vec<usize>),
+}
+
+impl<T> ElementaryOperation<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn is_row_operation(&self) -> bool {
+        return matches!(self, ElementaryOperation::SwapRows(..) | ElementaryOperation::ScaleRow { .. }
+            | ElementaryOperation::AddRowMultiple { .. } | ElementaryOperation::PermuteRows(_));
+    }
+
+    pub fn apply(&self, matrix: &mut Matrix<T>) {
+        match self {
+            ElementaryOperation::SwapRows(a, b) => matrix.swap_rows(*a, *b),
+            ElementaryOperation::ScaleRow { row, factor } => matrix.scale_row(*row, *factor),
+            ElementaryOperation::AddRowMultiple { target, source, factor } => matrix.add_row_multiple(*target, *source, *factor),
+            ElementaryOperation::PermuteRows(permutation) => matrix.permute_rows(permutation),
+            ElementaryOperation::SwapCols(a, b) => matrix.swap_cols(*a, *b),
+            ElementaryOperation::ScaleCol { col, factor } => matrix.scale_col(*col, *factor),
+            ElementaryOperation::AddColMultiple { target, source, factor } => matrix.add_col_multiple(*target, *source, *factor),
+            ElementaryOperation::PermuteCols(permutation) => matrix.permute_cols(permutation),
+        }
+    }
+
+    // 对单位矩阵做同样的变换即得到对应的 size x size 初等矩阵，行变换时 size 为行数，列变换时 size 为列数
+    pub fn to_matrix(&self, size: usize) -> Matrix<T> {
+        let mut result: Matrix<T> = Matrix::identity(size);
+        self.apply(&mut result);
+        return result;
+    }
+}
+
+// 支持 {:.3} 这样的精度参数，作用于系数
+impl<T: fmt::Display> fmt::Display for ElementaryOperation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let factor = |factor: &T| match f.precision() {
+            Some(precision) => format!("{:.*}", precision, factor),
+            None => format!("{}", factor),
+        };
+        match self {
+            ElementaryOperation::SwapRows(a, b) => write!(f, "R{} <-> R{}", a + 1, b + 1),
+            ElementaryOperation::ScaleRow { row, factor: k } => write!(f, "R{} <- ({}) R{}", row + 1, factor(k), row + 1),
+            ElementaryOperation::AddRowMultiple { target, source, factor: k } =>
+                write!(f, "R{} <- R{} + ({}) R{}", target + 1, target + 1, factor(k), source + 1),
+            ElementaryOperation::PermuteRows(permutation) => write!(f, "R <- R{:?}", permutation),
+            ElementaryOperation::SwapCols(a, b) => write!(f, "C{} <-> C{}", a + 1, b + 1),
+            ElementaryOperation::ScaleCol { col, factor: k } => write!(f, "C{} <- ({}) C{}", col + 1, factor(k), col + 1),
+            ElementaryOperation::AddColMultiple { target, source, factor: k } =>
+                write!(f, "C{} <- C{} + ({}) C{}", target + 1, target + 1, factor(k), source + 1),
+            ElementaryOperation::PermuteCols(permutation) => write!(f, "C <- C{:?}", permutation),
+        }
+    }
+}
+
+// 消元过程的观察者：选定主元时调用 pivot，每执行一步初等变换后以变换后的矩阵调用 operation
+pub trait EliminationObserver<T> {
+    fn pivot(&mut self, _col: usize, _row: usize, _value: T) {}
+    fn operation(&mut self, operation: &ElementaryOperation<T>, matrix: &Matrix<T>);
+}
+
+// 按顺序记录消元过程中执行的初等变换，之后可以逐步重放
+#[derive(Clone, Debug)]
+pub struct ElementaryRecorder<T> {
+    operations: Vec<ElementaryOperation<T>>,
+}
+
+impl<T> ElementaryRecorder<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn new() -> Self {
+        ElementaryRecorder { operations: Vec::new() }
+    }
+
+    pub fn record(&mut self, operation: ElementaryOperation<T>) {
+        self.operations.push(operation);
+    }
+
+    pub fn get_operations(&self) -> &Vec<ElementaryOperation<T>> {
+        return &self.operations;
+    }
+
+    // 每一步对应的初等矩阵，行变换为 rows x rows（左乘），列变换为 cols x cols（右乘）
+    pub fn elementary_matrices(&self, rows: usize, cols: usize) -> Vec<Matrix<T>> {
+        return self.operations.iter()
+            .map(|operation| operation.to_matrix(if operation.is_row_operation() { rows } else { cols }))
+            .collect();
+    }
+
+    // 从 start 开始依次执行记录的变换，返回每一步之后的矩阵
+    pub fn replay(&self, start: &Matrix<T>) -> Vec<Matrix<T>> {
+        let mut current = start.clone();
+        let mut steps: Vec<Matrix<T>> = Vec::with_capacity(self.operations.len());
+        for operation in &self.operations {
+            operation.apply(&mut current);
+            steps.push(current.clone());
+        }
+        return steps;
+    }
+}
+
+impl<T: Clone> EliminationObserver<T> for ElementaryRecorder<T> {
+    fn operation(&mut self, operation: &ElementaryOperation<T>, _matrix: &Matrix<T>) {
+        self.operations.push(operation.clone());
+    }
+}
+
+impl<T> Default for ElementaryRecorder<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    fn default() -> Self {
+        ElementaryRecorder::new()
+    }
+}
+
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn swap_rows(&mut self, from_row: usize, to_row: usize){
+        let mut cache:T;
+        for col in 0 .. self.get_cols() {
+            cache = *self.get(from_row, col);
+            self.set(from_row, col, *self.get(to_row, col));
+            self.set(to_row, col, cache);
+        }
+    }
+
+    pub fn scale_row(&mut self, row: usize, factor: T){
+        for col in 0..self.get_cols() {
+            self.set(row, col, *self.get(row, col) * factor);
+        }
+    }
+
+    pub fn add_row_multiple(&mut self, target: usize, source: usize, factor: T){
+        for col in 0..self.get_cols() {
+            self.set(target, col, *self.get(target, col) + factor * *self.get(source, col));
+        }
+    }
+
+    pub fn permute_rows(&mut self, permutation: &[usize]){
+        assert_permutation(permutation, self.get_rows());
+        let original = self.clone();
+        for (row, source) in permutation.iter().enumerate() {
+            for col in 0..self.get_cols() {
+                self.set(row, col, *original.get(*source, col));
+            }
+        }
+    }
+
+    pub fn swap_cols(&mut self, from_col: usize, to_col: usize){
+        let mut cache:T;
+        for row in 0 .. self.get_rows() {
+            cache = *self.get(row, from_col);
+            self.set(row, from_col, *self.get(row, to_col));
+            self.set(row, to_col, cache);
+        }
+    }
+
+    pub fn scale_col(&mut self, col: usize, factor: T){
+        for row in 0..self.get_rows() {
+            self.set(row, col, *self.get(row, col) * factor);
+        }
+    }
+
+    pub fn add_col_multiple(&mut self, target: usize, source: usize, factor: T){
+        for row in 0..self.get_rows() {
+            self.set(row, target, *self.get(row, target) + factor * *self.get(row, source));
+        }
+    }
+
+    pub fn permute_cols(&mut self, permutation: &[usize]){
+        assert_permutation(permutation, self.get_cols());
+        let original = self.clone();
+        for (col, source) in permutation.iter().enumerate() {
+            for row in 0..self.get_rows() {
+                self.set(row, col, *original.get(row, *source));
+            }
+        }
+    }
+
+    // 与 get_upper_triangular_matrix 相同，同时把每一步初等行变换记录到 recorder 中
+    pub fn get_upper_triangular_matrix_recorded(target:&Matrix<T>, recorder:&mut ElementaryRecorder<T>) -> (Matrix<T>, i64){
+        let (result, _, swap_ops) = Matrix::eliminate_to_upper_triangular(target, Some(recorder));
+        return (result, swap_ops);
+    }
+}
+
+fn assert_permutation(permutation: &[usize], size: usize) {
+    assert_eq!(permutation.len(), size, "置换的长度与矩阵大小不符");
+    let mut seen = vec![false; size];
+    for index in permutation {
+        assert!(*index < size && !seen[*index], "不是合法的置换: {:?}", permutation);
+        seen[*index] = true;
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use num_traits::One;
+use crate::elementary::{ElementaryOperation, EliminationObserver};
+use crate::matrix::Matrix;
+use crate::matrix_format::{MatrixFormatter, OutputStyle};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TraceStep<T> {
+    // 第 col 列选第 row 行的 value 作为主元
+    Pivot { col: usize, row: usize, value: T },
+    // 执行的初等变换以及变换之后的矩阵
+    Operation { operation: ElementaryOperation<T>, matrix: Matrix<T> },
+}
+
+// 高斯消元的完整过程，可以用方框格式或 LaTeX 输出为逐步的解题过程
+#[derive(Clone, Debug)]
+pub struct EliminationTrace<T> {
+    initial: Matrix<T>,
+    steps: Vec<TraceStep<T>>,
+    // 增广矩阵 [A | B] 中竖线左侧的列数
+    augmented_at: Option<usize>,
+}
+
+impl<T> EliminationObserver<T> for EliminationTrace<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    fn pivot(&mut self, col: usize, row: usize, value: T) {
+        self.steps.push(TraceStep::Pivot { col, row, value });
+    }
+
+    fn operation(&mut self, operation: &ElementaryOperation<T>, matrix: &Matrix<T>) {
+        self.steps.push(TraceStep::Operation { operation: operation.clone(), matrix: matrix.clone() });
+    }
+}
+
+impl<T> EliminationTrace<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn new(initial: &Matrix<T>, augmented_at: Option<usize>) -> Self {
+        EliminationTrace { initial: initial.clone(), steps: Vec::new(), augmented_at }
+    }
+
+    pub fn get_initial(&self) -> &Matrix<T> {
+        return &self.initial;
+    }
+
+    pub fn get_steps(&self) -> &Vec<TraceStep<T>> {
+        return &self.steps;
+    }
+
+    // 最后一步之后的矩阵
+    pub fn get_final(&self) -> &Matrix<T> {
+        return self.steps.iter().rev().find_map(|step| match step {
+            TraceStep::Operation { matrix, .. } => Some(matrix),
+            TraceStep::Pivot { .. } => None,
+        }).unwrap_or(&self.initial);
+    }
+
+    // LaTeX 样式输出一个 align* 环境，其余样式输出文字说明加上 formatter 格式化的矩阵
+    pub fn render(&self, formatter: &MatrixFormatter) -> String {
+        let value = |value: &T| match formatter.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => format!("{}", value),
+        };
+        let mut text = String::new();
+        if formatter.style == OutputStyle::Latex {
+            text.push_str("\\begin{align*}\n");
+            text.push_str(&format!("& {} \\\\\n", self.latex_matrix(&self.initial, formatter.precision)));
+            for step in &self.steps {
+                match step {
+                    TraceStep::Pivot { col, row, value: pivot } => text.push_str(&format!(
+                        "& \\text{{第 {} 列选第 {} 行的 {} 作为主元}} \\\\\n", col + 1, row + 1, value(pivot))),
+                    TraceStep::Operation { operation, matrix } => text.push_str(&format!("\\xrightarrow{{{}}} & {} \\\\\n",
+                        latex_operation(operation, &value), self.latex_matrix(matrix, formatter.precision))),
+                }
+            }
+            text.push_str("\\end{align*}\n");
+            return text;
+        }
+        text.push_str("初始矩阵\n");
+        text.push_str(&formatter.format(&self.initial));
+        for step in &self.steps {
+            match step {
+                TraceStep::Pivot { col, row, value: pivot } =>
+                    text.push_str(&format!("第 {} 列选第 {} 行的 {} 作为主元\n", col + 1, row + 1, value(pivot))),
+                TraceStep::Operation { operation, matrix } => {
+                    match formatter.precision {
+                        Some(precision) => text.push_str(&format!("{:.*}\n", precision, operation)),
+                        None => text.push_str(&format!("{}\n", operation)),
+                    }
+                    text.push_str(&formatter.format(matrix));
+                }
+            }
+        }
+        return text;
+    }
+
+    fn latex_matrix(&self, matrix: &Matrix<T>, precision: Option<usize>) -> String {
+        let cell = |row: usize, col: usize| match precision {
+            Some(precision) => format!("{:.*}", precision, matrix.get(row, col)),
+            None => format!("{}", matrix.get(row, col)),
+        };
+        let rows: Vec<String> = (0..matrix.get_rows())
+            .map(|row| (0..matrix.get_cols()).map(|col| cell(row, col)).collect::<Vec<String>>().join(" & "))
+            .collect();
+        return match self.augmented_at {
+            Some(split) => format!("\\left[\\begin{{array}}{{{}|{}}} {} \\end{{array}}\\right]",
+                "c".repeat(split), "c".repeat(matrix.get_cols() - split), rows.join(" \\\\ ")),
+            None => format!("\\begin{{bmatrix}} {} \\end{{bmatrix}}", rows.join(" \\\\ ")),
+        };
+    }
+}
+
+fn latex_operation<T>(operation: &ElementaryOperation<T>, value: &dyn Fn(&T) -> String) -> String {
+    return match operation {
+        ElementaryOperation::SwapRows(a, b) => format!("R_{{{}}} \\leftrightarrow R_{{{}}}", a + 1, b + 1),
+        ElementaryOperation::ScaleRow { row, factor } => format!("R_{{{}}} \\to ({}) R_{{{}}}", row + 1, value(factor), row + 1),
+        ElementaryOperation::AddRowMultiple { target, source, factor } =>
+            format!("R_{{{}}} \\to R_{{{}}} + ({}) R_{{{}}}", target + 1, target + 1, value(factor), source + 1),
+        ElementaryOperation::PermuteRows(permutation) => format!("R \\to R{:?}", permutation),
+        ElementaryOperation::SwapCols(a, b) => format!("C_{{{}}} \\leftrightarrow C_{{{}}}", a + 1, b + 1),
+        ElementaryOperation::ScaleCol { col, factor } => format!("C_{{{}}} \\to ({}) C_{{{}}}", col + 1, value(factor), col + 1),
+        ElementaryOperation::AddColMultiple { target, source, factor } =>
+            format!("C_{{{}}} \\to C_{{{}}} + ({}) C_{{{}}}", target + 1, target + 1, value(factor), source + 1),
+        ElementaryOperation::PermuteCols(permutation) => format!("C \\to C{:?}", permutation),
+    };
+}
+
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    // 与 get_upper_triangular_matrix 相同，额外返回每一步的主元、行变换和中间矩阵
+    pub fn get_upper_triangular_matrix_traced(target:&Matrix<T>) -> (Matrix<T>, i64, EliminationTrace<T>){
+        let mut trace = EliminationTrace::new(target, None);
+        let (result, _, swap_ops) = Matrix::eliminate_to_upper_triangular(target, Some(&mut trace));
+        return (result, swap_ops, trace);
+    }
+
+    // 对增广矩阵 [A | I] 做带部分主元的 Gauss-Jordan 消元求逆，矩阵奇异时返回 None，
+    // 此时 trace 记录到发现奇异为止
+    pub fn inverse_traced(target:&Matrix<T>) -> (Option<Matrix<T>>, EliminationTrace<T>){
+        assert_eq!(target.get_rows(), target.get_cols(), "只有方阵可以求逆");
+        let size = target.get_rows();
+        let mut augmented: Matrix<T> = Matrix::new(size, 2 * size);
+        for row in 0..size {
+            for col in 0..size {
+                augmented.set(row, col, *target.get(row, col));
+            }
+            augmented.set(row, size + row, T::one());
+        }
+        let mut trace = EliminationTrace::new(&augmented, Some(size));
+        for col in 0..size {
+            let mut pivot_index = col;
+            for row in col + 1..size {
+                if Self::abs_of(*augmented.get(row, col)) > Self::abs_of(*augmented.get(pivot_index, col)) {
+                    pivot_index = row;
+                }
+            }
+            let pivot = *augmented.get(pivot_index, col);
+            if pivot == T::default() {
+                return (None, trace);
+            }
+            trace.pivot(col, pivot_index, pivot);
+            let mut operations: Vec<ElementaryOperation<T>> = Vec::new();
+            if pivot_index != col {
+                operations.push(ElementaryOperation::SwapRows(pivot_index, col));
+            }
+            if pivot != T::one() {
+                operations.push(ElementaryOperation::ScaleRow { row: col, factor: T::one() / pivot });
+            }
+            for operation in operations {
+                operation.apply(&mut augmented);
+                trace.operation(&operation, &augmented);
+            }
+            for row in 0..size {
+                let factor = *augmented.get(row, col);
+                if row == col || factor == T::default() {
+                    continue;
+                }
+                let operation = ElementaryOperation::AddRowMultiple { target: row, source: col, factor: T::default() - factor };
+                operation.apply(&mut augmented);
+                trace.operation(&operation, &augmented);
+            }
+        }
+        let mut inverse: Matrix<T> = Matrix::new(size, size);
+        for row in 0..size {
+            for col in 0..size {
+                inverse.set(row, col, *augmented.get(row, size + col));
+            }
+        }
+        return (Some(inverse), trace);
+    }
+}
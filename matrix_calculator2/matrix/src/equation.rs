@@ -1,86 +1,235 @@
 use std::str::FromStr;
 use bigdecimal::BigDecimal;
-use num_traits::ToPrimitive;
+use num_traits::{ToPrimitive, Zero};
 use crate::complex::Complex;
+use crate::matrix::Matrix;
+use crate::polynomial::Polynomial;
+use crate::root_finder;
 
-pub struct EquationWithDegrees {
-    parameters: Vec<Complex>,
+// 方程 p(x) = 0 直接用它左侧的多项式表示，求根等方法定义在 Polynomial 上
+pub type EquationWithDegrees = Polynomial;
+
+// 求根算法
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RootFinder {
+    // 所有根同时迭代，线性到二次收敛，初值在单位圆上
+    #[default]
+    DurandKerner,
+    // 所有根同时迭代，三阶收敛，初值的半径由 Cauchy 上下界确定
+    AberthEhrlich,
+    // 逐个求根并降阶
+    JenkinsTraub,
+}
+
+// 求根的控制参数
+#[derive(Clone, Debug)]
+pub struct SolveOptions {
+    pub method: RootFinder,
+    // Jenkins–Traub 中为每个根的迭代次数上限
+    pub max_iterations: usize,
+    // 所有根处 |p(x)| 都不超过它时认为收敛
+    pub residual_tolerance: BigDecimal,
+    // 一次迭代中所有根的移动距离都不超过它时也认为收敛
+    pub step_tolerance: BigDecimal,
+    // 工作精度，每次迭代后的根与中间结果保留的小数位数
+    pub precision: i64,
+    // 次数不超过 4 时直接用求根公式，再做几步 Newton 修正，不再迭代
+    pub closed_form: bool,
 }
 
-impl EquationWithDegrees{
-    pub fn new(parameters: Vec<Complex>) -> EquationWithDegrees{
-        EquationWithDegrees{
-            parameters,
+impl Default for SolveOptions {
+    fn default() -> Self {
+        SolveOptions {
+            method: RootFinder::DurandKerner,
+            max_iterations: 500,
+            residual_tolerance: BigDecimal::from_str("0.0000001").unwrap(),
+            step_tolerance: BigDecimal::from_str("0.000000000000000000001").unwrap(),
+            precision: 30,
+            closed_form: true,
         }
     }
+}
+
+// 每次迭代之前传给回调的状态，第 0 次为初值
+pub struct SolveIteration<'a> {
+    pub iteration: usize,
+    pub roots: &'a [Complex],
+    pub residuals: &'a [BigDecimal],
+}
+
+#[derive(Clone, Debug)]
+pub struct SolveReport {
+    // 实际执行的迭代次数
+    pub iterations: usize,
+    // 返回的每个根处的 |p(x)|，与根的顺序相同
+    pub residuals: Vec<BigDecimal>,
+    // 最后一次迭代中根移动的最大距离，没有迭代时为 None
+    pub last_step: Option<BigDecimal>,
+    pub converged: bool,
+}
+
+impl SolveReport {
+    // 残差或者最后一步的步长满足 options 中的要求
+    pub(crate) fn within_tolerance(&self, options:&SolveOptions) -> bool {
+        let small_residual = self.residuals.iter().all(|residual| *residual <= options.residual_tolerance);
+        let small_step = self.last_step.as_ref().is_some_and(|step| *step <= options.step_tolerance);
+        return small_residual || small_step;
+    }
+}
 
+impl Polynomial{
+    // 按默认参数求根，残差不超过 threshold 时停止；达到最大迭代次数时返回当前的近似值
     pub fn solve(&self, threshold:&BigDecimal) -> Vec<Complex>{
-        let mut roots = Self::initial_roots(self.parameters.len() - 1);
-        let mut temp_roots = Vec::new();
+        let options = SolveOptions { residual_tolerance: threshold.clone(), ..SolveOptions::default() };
+        return self.solve_with_options(&options, None).0;
+    }
+
+    // 按 options.method 选择算法求根，残差按首一化之后的多项式计算；options.closed_form 时四次以下直接用公式。
+    // 同时迭代的算法在每次迭代之前调用 callback，Jenkins–Traub 在每求出一个根之后调用
+    pub fn solve_with_options(&self, options:&SolveOptions, callback: Option<&mut dyn FnMut(&SolveIteration)>) -> (Vec<Complex>, SolveReport){
+        let monic = self.monic();
+        if monic.degree() == 0 {
+            return (Vec::new(), SolveReport { iterations: 0, residuals: Vec::new(), last_step: None, converged: true });
+        }
+        if options.closed_form {
+            if let Some(roots) = monic.solve_closed_form() {
+                return Self::polish_closed_form(&monic, roots, options, callback);
+            }
+        }
+        return match options.method {
+            RootFinder::DurandKerner => Self::durand_kerner(&monic, options, callback),
+            RootFinder::AberthEhrlich => root_finder::aberth_ehrlich(&monic, options, callback),
+            RootFinder::JenkinsTraub => root_finder::jenkins_traub(&monic, options, callback),
+        };
+    }
+
+    // 公式解在原多项式上用 Newton 法修正，回调只调用一次
+    fn polish_closed_form(monic:&Polynomial, roots:Vec<Complex>, options:&SolveOptions, callback: Option<&mut dyn FnMut(&SolveIteration)>) -> (Vec<Complex>, SolveReport){
+        let derivative = monic.derivative(1);
+        let roots:Vec<Complex> = roots.into_iter().map(|root| root_finder::polish(monic, &derivative, root.round(options.precision), options)).collect();
+        let residuals = roots.iter().map(|root| monic.evaluate(root).abs()).collect();
+        let mut report = SolveReport { iterations: 0, residuals, last_step: None, converged: false };
+        report.converged = report.within_tolerance(options);
+        if let Some(callback) = callback {
+            callback(&SolveIteration { iteration: 0, roots: &roots, residuals: &report.residuals });
+        }
+        return (roots, report);
+    }
+
+    fn durand_kerner(monic:&Polynomial, options:&SolveOptions, mut callback: Option<&mut dyn FnMut(&SolveIteration)>) -> (Vec<Complex>, SolveReport){
+        let mut roots = Self::initial_roots(monic.degree());
+        let mut report = SolveReport { iterations: 0, residuals: Vec::new(), last_step: None, converged: false };
         loop {
-            let mut errors:Vec<BigDecimal> = Vec::new();
-            for root in &roots {
-                let res = Self::calc_eq(self, root);
-                res.real.round(50);
-                res.imaginary.round(50);
-                let error = res.abs();
-                errors.push(error);
-                let root_prod = Self::calc_root_prods(&roots, root);
-                root_prod.real.round(50);
-                root_prod.imaginary.round(50);
-                let new_root = root - &(&res / &root_prod);
-                new_root.real.round(20);
-                new_root.imaginary.round(20);
-                temp_roots.push(new_root);
+            let values:Vec<Complex> = roots.iter().map(|root| monic.evaluate(root).round(options.precision)).collect();
+            report.residuals = values.iter().map(|value| value.abs()).collect();
+            if let Some(callback) = callback.as_mut() {
+                callback(&SolveIteration { iteration: report.iterations, roots: &roots, residuals: &report.residuals });
             }
-            roots = temp_roots.clone();
-            temp_roots = Vec::new();
-            let mut under_threshold = true;
-            print!("Errors:");
-            for error_index in 0..errors.len() {
-                let error = errors.get(error_index).unwrap();
-                let print_error = error.clone();
-                print_error.round(4);
-                print!("{} ",print_error.to_f64().unwrap());
-                if error.abs() > *threshold { 
-                    under_threshold = false;
-                }
+            if report.within_tolerance(options) {
+                report.converged = true;
+                break;
             }
-            errors = Vec::new();
-            print!("\n");
-            if under_threshold { 
+            if report.iterations >= options.max_iterations {
                 break;
             }
+            let mut next_roots:Vec<Complex> = Vec::with_capacity(roots.len());
+            let mut last_step = BigDecimal::from(0);
+            for (index, (root, value)) in roots.iter().zip(&values).enumerate() {
+                let root_prod = Self::calc_root_prods(&roots, root).round(options.precision);
+                // 两个近似根重合时分母为零，按下标把它们错开一点，下一轮再继续迭代
+                let step = if root_prod.is_zero() {
+                    Complex::init(0.0, -0.001 * (index + 1) as f64)
+                } else {
+                    (value / &root_prod).round(options.precision)
+                };
+                last_step = last_step.max(step.abs());
+                next_roots.push((root - &step).round(options.precision));
+            }
+            roots = next_roots;
+            report.last_step = Some(last_step);
+            report.iterations += 1;
         }
-        return roots;
+        return (roots, report);
+    }
+
+    // 首一化之后 x^n + c_{n-1} x^{n-1} + ... + c_0 的友矩阵：第一行为 -c_{n-1}, ..., -c_0，次对角线为 1，
+    // 它的特征多项式就是这个多项式。系数有虚部或者次数为 0 时返回 None
+    pub fn companion_matrix(&self) -> Option<Matrix<f64>>{
+        let monic = self.monic();
+        let degree = monic.degree();
+        if degree == 0 || monic.get_coefficients().iter().any(|coefficient| !coefficient.imaginary.is_zero()) {
+            return None;
+        }
+        let mut result: Matrix<f64> = Matrix::new(degree, degree);
+        for col in 0..degree {
+            let coefficient = &monic.get_coefficients()[degree - 1 - col].real;
+            result.set(0, col, -coefficient.to_f64().unwrap_or(f64::NAN));
+        }
+        for row in 1..degree {
+            result.set(row, row - 1, 1.0);
+        }
+        return Some(result);
+    }
+
+    // 平衡友矩阵之后用 QR 迭代求全部特征值，即多项式的全部根。
+    // 只支持实系数多项式，精度受 f64 限制；系数有虚部、超出 f64 的范围或者 QR 迭代不收敛时返回 None
+    pub fn solve_via_companion(&self) -> Option<Vec<Complex>>{
+        if self.monic().degree() == 0 {
+            return Some(Vec::new());
+        }
+        let companion = Matrix::balance(&self.companion_matrix()?);
+        let eigenvalues = Matrix::eigenvalues(&companion)?;
+        if eigenvalues.iter().any(|(real, imaginary)| !real.is_finite() || !imaginary.is_finite()) {
+            return None;
+        }
+        return Some(eigenvalues.iter().map(|(real, imaginary)| Complex::init(*real, *imaginary)).collect());
+    }
+
+    // 先做无平方分解再对每个因式分别求根，返回互不相同的根及其重数。
+    // 重根处 Durand–Kerner 只有线性收敛，分解之后每个因式都只有单根。
+    // threshold 同时作为求最大公因式时判断余数为零的相对误差
+    pub fn solve_with_multiplicities(&self, threshold:&BigDecimal) -> Vec<(Complex, usize)>{
+        let mut result:Vec<(Complex, usize)> = Vec::new();
+        for (factor, multiplicity) in self.square_free_decomposition(threshold) {
+            if factor.degree() == 1 {
+                // 首一的一次因式 x + c 的根为 -c
+                result.push((&Complex::new() - &factor.get_coefficients()[0], multiplicity));
+                continue;
+            }
+            for root in factor.solve(threshold) {
+                result.push((root, multiplicity));
+            }
+        }
+        return result;
+    }
+
+    // 驻点，即导数互不相同的根及其在导数中的重数。实驻点的重数为奇数时是极值点，为偶数时不是；
+    // 次数小于 2 时没有驻点
+    pub fn critical_points(&self, threshold:&BigDecimal) -> Vec<(Complex, usize)>{
+        if self.degree() < 2 {
+            return Vec::new();
+        }
+        return self.derivative(1).solve_with_multiplicities(threshold);
     }
 
     fn initial_roots(size:usize) -> Vec<Complex>{
-        let PI = BigDecimal::from_str("3.141592653589793238462643383279").unwrap();
+        let pi = BigDecimal::from_str("3.141592653589793238462643383279").unwrap();
+        // 整体旋转一个角度，避免实系数多项式的初值全落在实轴上，迭代永远到不了复根
+        let offset = BigDecimal::from_str("0.4").unwrap();
         let mut result:Vec<Complex> = Vec::new();
         for i in 1..=size {
-            let real =  Complex::calc_cos(&((2 * i as i64 * &PI) / size as i64), 4).round(10);
-            let imaginary = Complex::calc_sin(&((2 * i as i64 * &PI) / size as i64), 4).round(10);
+            let angle = (2 * i as i64 * &pi) / size as i64 + &offset;
+            let real =  Complex::calc_cos(&angle, 30).round(10);
+            let imaginary = Complex::calc_sin(&angle, 30).round(10);
             let comp = Complex::from_big_decimal(&real, &imaginary);
             result.push(comp);
         }
         return result;
     }
 
-    fn calc_eq(eq:&EquationWithDegrees, x:&Complex) -> Complex{
-        let mut result = Complex::from_big_decimal(&eq.parameters.get(0).unwrap().real, &eq.parameters.get(0).unwrap().imaginary);
-        for pow in 1 .. eq.parameters.len() as i32 {
-            let parameter = eq.parameters.get(pow as usize).unwrap();
-            result = &result + &(parameter * &Complex::complex_pow(&x, pow));
-        }
-        return result;
-    }
-
     fn calc_root_prods(roots:&Vec<Complex>, current_root:&Complex) -> Complex{
         let mut result = Complex::init(1.0, 0.0);
         for root in roots {
-            root.real.round(50);
-            root.imaginary.round(50);
             if root.real != current_root.real || root.imaginary != current_root.imaginary {
                 result = &result * &(current_root - root);
             }
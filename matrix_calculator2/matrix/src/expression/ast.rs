@@ -0,0 +1,49 @@
+use super::Span;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Hadamard,
+}
+
+impl BinaryOperator {
+    pub fn symbol(&self) -> &'static str {
+        return match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Power => "^",
+            BinaryOperator::Hadamard => ".*",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ExprKind {
+    Number(f64),
+    Imaginary(f64),
+    Variable(String),
+    // 矩阵字面量，每个元素都应求值为实数
+    Matrix(Vec<Vec<Expr>>),
+    Negate(Box<Expr>),
+    Transpose(Box<Expr>),
+    Binary { operator: BinaryOperator, lhs: Box<Expr>, rhs: Box<Expr> },
+    Call { function: String, arguments: Vec<Expr> },
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Statement {
+    Assign { name: String, value: Expr },
+    Expression(Expr),
+}
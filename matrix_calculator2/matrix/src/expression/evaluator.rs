@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use crate::complex::Complex;
+use crate::polynomial::Polynomial;
+use crate::polynomial_parser::MAX_DEGREE;
+use crate::matrix::Matrix;
+use super::ast::{BinaryOperator, Expr, ExprKind, Statement};
+use super::parser::parse_program;
+use super::{ErrorKind, ExpressionError, Span};
+
+#[derive(Clone)]
+pub enum Value {
+    Scalar(f64),
+    Complex(Complex),
+    Matrix(Matrix<f64>),
+    Polynomial(Polynomial),
+    Roots(Vec<Complex>),
+}
+
+impl Value {
+    pub fn type_name(&self) -> String {
+        return match self {
+            Value::Scalar(_) => "实数".to_string(),
+            Value::Complex(_) => "复数".to_string(),
+            Value::Matrix(matrix) => format!("{}x{} 矩阵", matrix.get_rows(), matrix.get_cols()),
+            Value::Polynomial(_) => "多项式".to_string(),
+            Value::Roots(roots) => format!("{} 个根", roots.len()),
+        }
+    }
+}
+
+pub struct Interpreter {
+    variables: HashMap<String, Value>,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter {
+            variables: HashMap::new(),
+        }
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<&Value> {
+        return self.variables.get(name);
+    }
+
+    pub fn set_variable(&mut self, name: &str, value: Value) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    pub fn get_variables(&self) -> &HashMap<String, Value> {
+        return &self.variables;
+    }
+
+    // 依次执行源码中的每条语句，返回最后一条语句的值
+    pub fn run(&mut self, source: &str) -> Result<Option<Value>, ExpressionError> {
+        let mut last: Option<Value> = None;
+        for statement in parse_program(source)? {
+            last = Some(self.execute(&statement)?);
+        }
+        return Ok(last);
+    }
+
+    pub fn execute(&mut self, statement: &Statement) -> Result<Value, ExpressionError> {
+        return match statement {
+            Statement::Assign { name, value } => {
+                let result = self.evaluate(value)?;
+                self.variables.insert(name.clone(), result.clone());
+                Ok(result)
+            }
+            Statement::Expression(expr) => {
+                let result = self.evaluate(expr)?;
+                self.variables.insert("ans".to_string(), result.clone());
+                Ok(result)
+            }
+        }
+    }
+
+    pub fn evaluate(&self, expr: &Expr) -> Result<Value, ExpressionError> {
+        return match &expr.kind {
+            ExprKind::Number(value) => Ok(Value::Scalar(*value)),
+            ExprKind::Imaginary(value) => Ok(Value::Complex(Complex::init(0.0, *value))),
+            ExprKind::Variable(name) => match self.variables.get(name) {
+                Some(value) => Ok(value.clone()),
+                // 未被赋值时 i 是虚数单位，x 是多项式的自变量
+                None if name == "i" => Ok(Value::Complex(Complex::init(0.0, 1.0))),
+                None if name == "x" => Ok(Value::Polynomial(Polynomial::from_f64(&[0.0, 1.0]))),
+                None => Err(ExpressionError::new(ErrorKind::UndefinedVariable(name.clone()), expr.span)),
+            },
+            ExprKind::Matrix(rows) => self.matrix_literal(rows, expr.span),
+            ExprKind::Negate(operand) => {
+                let value = self.evaluate(operand)?;
+                multiply(&Value::Scalar(-1.0), &value, expr.span)
+            }
+            ExprKind::Transpose(operand) => match self.evaluate(operand)? {
+                Value::Matrix(matrix) => Ok(Value::Matrix(Matrix::transpose(&matrix))),
+                Value::Complex(number) => Ok(Value::Complex(Complex::conjugate(&number))),
+                Value::Scalar(number) => Ok(Value::Scalar(number)),
+                other => Err(type_mismatch("转置", &other, None, expr.span)),
+            },
+            ExprKind::Binary { operator, lhs, rhs } => {
+                let lhs = self.evaluate(lhs)?;
+                let rhs = self.evaluate(rhs)?;
+                match operator {
+                    BinaryOperator::Add => add(&lhs, &rhs, false, expr.span),
+                    BinaryOperator::Subtract => add(&lhs, &rhs, true, expr.span),
+                    BinaryOperator::Multiply => multiply(&lhs, &rhs, expr.span),
+                    BinaryOperator::Divide => divide(&lhs, &rhs, expr.span),
+                    BinaryOperator::Power => power(&lhs, &rhs, expr.span),
+                    BinaryOperator::Hadamard => hadamard(&lhs, &rhs, expr.span),
+                }
+            }
+            ExprKind::Call { function, arguments } => {
+                let mut values: Vec<Value> = Vec::with_capacity(arguments.len());
+                for argument in arguments {
+                    values.push(self.evaluate(argument)?);
+                }
+                call_function(function, values, expr.span)
+            }
+        }
+    }
+
+    fn matrix_literal(&self, rows: &[Vec<Expr>], span: Span) -> Result<Value, ExpressionError> {
+        if rows.is_empty() {
+            return Err(ExpressionError::new(ErrorKind::InvalidArgument("矩阵不能为空".to_string()), span));
+        }
+        let cols = rows[0].len();
+        let mut data: Vec<f64> = Vec::with_capacity(rows.len() * cols);
+        for (row_index, row) in rows.iter().enumerate() {
+            if row.len() != cols {
+                let row_span = row[0].span.merge(row[row.len() - 1].span);
+                return Err(ExpressionError::new(ErrorKind::InvalidArgument(
+                    format!("第 {} 行有 {} 个元素，应为 {} 个", row_index + 1, row.len(), cols)), row_span));
+            }
+            for element in row {
+                match self.evaluate(element)? {
+                    Value::Scalar(value) => data.push(value),
+                    other => return Err(ExpressionError::new(ErrorKind::InvalidArgument(
+                        format!("矩阵元素必须是实数，实际为{}", other.type_name())), element.span)),
+                }
+            }
+        }
+        return Ok(Value::Matrix(Matrix::init(rows.len(), cols, data)));
+    }
+}
+
+fn type_mismatch(operation: &str, lhs: &Value, rhs: Option<&Value>, span: Span) -> ExpressionError {
+    return ExpressionError::new(ErrorKind::TypeMismatch {
+        operation: operation.to_string(),
+        lhs: lhs.type_name(),
+        rhs: rhs.map(|value| value.type_name()).unwrap_or_default(),
+    }, span);
+}
+
+fn dimension_mismatch(operation: &str, lhs: &Matrix<f64>, rhs: &Matrix<f64>, span: Span) -> ExpressionError {
+    return ExpressionError::new(ErrorKind::DimensionMismatch {
+        operation: operation.to_string(),
+        lhs: (lhs.get_rows(), lhs.get_cols()),
+        rhs: (rhs.get_rows(), rhs.get_cols()),
+    }, span);
+}
+
+fn require_square(operation: &str, matrix: &Matrix<f64>, span: Span) -> Result<(), ExpressionError> {
+    if matrix.get_rows() != matrix.get_cols() {
+        return Err(ExpressionError::new(ErrorKind::NotSquare {
+            operation: operation.to_string(),
+            rows: matrix.get_rows(),
+            cols: matrix.get_cols(),
+        }, span));
+    }
+    return Ok(());
+}
+
+fn invert(matrix: &Matrix<f64>, span: Span) -> Result<Matrix<f64>, ExpressionError> {
+    require_square("求逆", matrix, span)?;
+    let identity: Matrix<f64> = Matrix::identity(matrix.get_rows());
+    return Matrix::solve(matrix, &identity).ok_or(ExpressionError::new(ErrorKind::SingularMatrix, span));
+}
+
+fn to_complex(value: &Value) -> Option<Complex> {
+    return match value {
+        Value::Scalar(number) => Some(Complex::init(*number, 0.0)),
+        Value::Complex(number) => Some(number.clone()),
+        _ => None,
+    }
+}
+
+// 标量、复数与多项式都可以视为多项式参与运算
+fn to_polynomial(value: &Value) -> Option<Polynomial> {
+    return match value {
+        Value::Polynomial(polynomial) => Some(polynomial.clone()),
+        other => to_complex(other).map(|number| Polynomial::constant(&number)),
+    }
+}
+
+fn add(lhs: &Value, rhs: &Value, subtract: bool, span: Span) -> Result<Value, ExpressionError> {
+    let operation = if subtract { "减法" } else { "加法" };
+    return match (lhs, rhs) {
+        (Value::Scalar(a), Value::Scalar(b)) => Ok(Value::Scalar(if subtract { a - b } else { a + b })),
+        (Value::Matrix(a), Value::Matrix(b)) => {
+            if a.get_rows() != b.get_rows() || a.get_cols() != b.get_cols() {
+                return Err(dimension_mismatch(operation, a, b, span));
+            }
+            Ok(Value::Matrix(if subtract { a - b } else { a + b }))
+        }
+        (Value::Polynomial(_), _) | (_, Value::Polynomial(_)) => match (to_polynomial(lhs), to_polynomial(rhs)) {
+            (Some(a), Some(b)) => Ok(Value::Polynomial(if subtract { &a - &b } else { &a + &b })),
+            _ => Err(type_mismatch(operation, lhs, Some(rhs), span)),
+        },
+        _ => match (to_complex(lhs), to_complex(rhs)) {
+            (Some(a), Some(b)) => Ok(Value::Complex(if subtract { &a - &b } else { &a + &b })),
+            _ => Err(type_mismatch(operation, lhs, Some(rhs), span)),
+        },
+    }
+}
+
+fn multiply(lhs: &Value, rhs: &Value, span: Span) -> Result<Value, ExpressionError> {
+    return match (lhs, rhs) {
+        (Value::Scalar(a), Value::Scalar(b)) => Ok(Value::Scalar(a * b)),
+        (Value::Matrix(a), Value::Matrix(b)) => {
+            if a.get_cols() != b.get_rows() {
+                return Err(dimension_mismatch("乘法", a, b, span));
+            }
+            Ok(Value::Matrix(a * b))
+        }
+        (Value::Matrix(a), Value::Scalar(b)) | (Value::Scalar(b), Value::Matrix(a)) => Ok(Value::Matrix(a * *b)),
+        (Value::Polynomial(_), _) | (_, Value::Polynomial(_)) => match (to_polynomial(lhs), to_polynomial(rhs)) {
+            (Some(a), Some(b)) => Ok(Value::Polynomial(&a * &b)),
+            _ => Err(type_mismatch("乘法", lhs, Some(rhs), span)),
+        },
+        _ => match (to_complex(lhs), to_complex(rhs)) {
+            (Some(a), Some(b)) => Ok(Value::Complex(&a * &b)),
+            _ => Err(type_mismatch("乘法", lhs, Some(rhs), span)),
+        },
+    }
+}
+
+fn divide(lhs: &Value, rhs: &Value, span: Span) -> Result<Value, ExpressionError> {
+    if let Some(divisor) = to_complex(rhs) {
+        if divisor.is_zero() {
+            return Err(ExpressionError::new(ErrorKind::DivisionByZero, span));
+        }
+    }
+    return match (lhs, rhs) {
+        (Value::Scalar(a), Value::Scalar(b)) => Ok(Value::Scalar(a / b)),
+        (Value::Matrix(a), Value::Scalar(b)) => Ok(Value::Matrix(a * (1.0 / b))),
+        // A / B = A * inv(B)，通过解 B' X' = A' 得到
+        (Value::Matrix(a), Value::Matrix(b)) => {
+            require_square("除法", b, span)?;
+            if a.get_cols() != b.get_rows() {
+                return Err(dimension_mismatch("除法", a, b, span));
+            }
+            let solution = Matrix::solve(&Matrix::transpose(b), &Matrix::transpose(a))
+                .ok_or(ExpressionError::new(ErrorKind::SingularMatrix, span))?;
+            Ok(Value::Matrix(Matrix::transpose(&solution)))
+        }
+        (Value::Polynomial(polynomial_value), _) => match to_complex(rhs) {
+            Some(divisor) => Ok(Value::Polynomial(polynomial_value / &divisor)),
+            None => Err(type_mismatch("除法", lhs, Some(rhs), span)),
+        },
+        _ => match (to_complex(lhs), to_complex(rhs)) {
+            (Some(a), Some(b)) => Ok(Value::Complex(&a / &b)),
+            _ => Err(type_mismatch("除法", lhs, Some(rhs), span)),
+        },
+    }
+}
+
+fn power(lhs: &Value, rhs: &Value, span: Span) -> Result<Value, ExpressionError> {
+    let exponent = match rhs {
+        Value::Scalar(exponent) => *exponent,
+        _ => return Err(type_mismatch("乘方", lhs, Some(rhs), span)),
+    };
+    let integer = exponent.fract() == 0.0 && exponent.abs() <= i32::MAX as f64;
+    return match lhs {
+        Value::Scalar(base) => Ok(Value::Scalar(base.powf(exponent))),
+        Value::Matrix(base) => {
+            require_square("乘方", base, span)?;
+            if integer && exponent < 0.0 {
+                let inverse = invert(base, span)?;
+                return Ok(Value::Matrix(Matrix::pow(&inverse, exponent.abs() as u32)));
+            }
+            if integer {
+                return Ok(Value::Matrix(Matrix::pow(base, exponent as u32)));
+            }
+            // logm 要求矩阵可逆且没有负实特征值
+            Matrix::powf(base, exponent).map(Value::Matrix).ok_or(ExpressionError::new(
+                ErrorKind::InvalidArgument("矩阵奇异或有负实特征值，无法求非整数次幂".to_string()), span))
+        }
+        Value::Complex(base) if integer => {
+            let result = Complex::complex_pow(base, exponent.abs() as i32);
+            if exponent >= 0.0 {
+                return Ok(Value::Complex(result));
+            }
+            if result.is_zero() {
+                return Err(ExpressionError::new(ErrorKind::DivisionByZero, span));
+            }
+            Ok(Value::Complex(&Complex::init(1.0, 0.0) / &result))
+        }
+        Value::Polynomial(base) if integer && exponent >= 0.0 => {
+            // 结果的次数不能超过多项式解析允许的上限，常数多项式按 1 次计算
+            if exponent * base.degree().max(1) as f64 > MAX_DEGREE as f64 {
+                return Err(ExpressionError::new(
+                    ErrorKind::InvalidArgument(format!("多项式乘方后的次数超过了上限 {}", MAX_DEGREE)), span));
+            }
+            // 反复平方，乘法次数与指数的位数成正比
+            let mut result = Polynomial::from_f64(&[1.0]);
+            let mut square = base.clone();
+            let mut remaining = exponent as usize;
+            while remaining > 0 {
+                if remaining % 2 == 1 {
+                    result = &result * &square;
+                }
+                remaining /= 2;
+                if remaining > 0 {
+                    square = &square * &square;
+                }
+            }
+            Ok(Value::Polynomial(result))
+        }
+        Value::Complex(_) | Value::Polynomial(_) => Err(ExpressionError::new(
+            ErrorKind::InvalidArgument(format!("{}只能求非负整数次幂", lhs.type_name())), span)),
+        _ => Err(type_mismatch("乘方", lhs, Some(rhs), span)),
+    }
+}
+
+fn hadamard(lhs: &Value, rhs: &Value, span: Span) -> Result<Value, ExpressionError> {
+    return match (lhs, rhs) {
+        (Value::Matrix(a), Value::Matrix(b)) => {
+            if a.get_rows() != b.get_rows() || a.get_cols() != b.get_cols() {
+                return Err(dimension_mismatch("逐元素乘法", a, b, span));
+            }
+            Ok(Value::Matrix(Matrix::hadamard_product(a.clone(), b.clone())))
+        }
+        _ => multiply(lhs, rhs, span),
+    }
+}
+
+fn call_function(function: &str, mut arguments: Vec<Value>, span: Span) -> Result<Value, ExpressionError> {
+    let expected = match function {
+        "det" | "determinant" | "inv" | "inverse" | "transpose" | "rref" | "exp" | "abs" | "conj" | "roots" => 1,
+        "eval" => 2,
+        _ => return Err(ExpressionError::new(ErrorKind::UnknownFunction(function.to_string()), span)),
+    };
+    if arguments.len() != expected {
+        return Err(ExpressionError::new(ErrorKind::ArgumentCount {
+            function: function.to_string(),
+            expected,
+            found: arguments.len(),
+        }, span));
+    }
+    let argument = arguments.remove(0);
+    return match (function, &argument) {
+        ("det" | "determinant", Value::Matrix(matrix)) => {
+            require_square("行列式", matrix, span)?;
+            Ok(Value::Scalar(Matrix::lu(matrix).determinant()))
+        }
+        ("inv" | "inverse", Value::Matrix(matrix)) => Ok(Value::Matrix(invert(matrix, span)?)),
+        ("inv" | "inverse", _) => divide(&Value::Scalar(1.0), &argument, span),
+        ("transpose", Value::Matrix(matrix)) => Ok(Value::Matrix(Matrix::transpose(matrix))),
+        ("rref", Value::Matrix(matrix)) => Ok(Value::Matrix(Matrix::rref(matrix))),
+        ("exp", Value::Scalar(number)) => Ok(Value::Scalar(number.exp())),
+        ("exp", Value::Complex(number)) => Ok(Value::Complex(Complex::exp(number))),
+        ("exp", Value::Matrix(matrix)) => {
+            require_square("矩阵指数", matrix, span)?;
+            Matrix::expm(matrix).map(Value::Matrix).ok_or(ExpressionError::new(ErrorKind::SingularMatrix, span))
+        }
+        ("abs", Value::Scalar(number)) => Ok(Value::Scalar(number.abs())),
+        ("abs", Value::Complex(number)) => Ok(Value::Scalar(number.abs().to_f64().unwrap_or(f64::NAN))),
+        ("conj", Value::Scalar(_)) => Ok(argument.clone()),
+        ("conj", Value::Complex(number)) => Ok(Value::Complex(Complex::conjugate(number))),
+        ("roots", Value::Polynomial(polynomial)) => {
+            if polynomial.degree() < 1 {
+                return Err(ExpressionError::new(ErrorKind::InvalidArgument("多项式的次数至少为 1".to_string()), span));
+            }
+            let threshold = BigDecimal::from_str("0.0000001").unwrap();
+            Ok(Value::Roots(polynomial.solve(&threshold)))
+        }
+        ("eval", Value::Polynomial(polynomial)) => {
+            let point = to_complex(&arguments[0]).ok_or(type_mismatch("eval", &argument, Some(&arguments[0]), span))?;
+            Ok(Value::Complex(polynomial.evaluate(&point)))
+        }
+        _ => Err(type_mismatch(function, &argument, None, span)),
+    }
+}
@@ -0,0 +1,135 @@
+use super::{ErrorKind, ExpressionError, Span};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TokenKind {
+    Number(f64),
+    Imaginary(f64),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    DotStar,
+    Apostrophe,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Semicolon,
+    Equals,
+    Newline,
+    End,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+    // 矩阵字面量中用空格分隔元素，需要知道记号前后是否有空白
+    pub space_before: bool,
+}
+
+impl TokenKind {
+    pub fn describe(&self) -> String {
+        return match self {
+            TokenKind::Number(value) => format!("数字 {}", value),
+            TokenKind::Imaginary(value) => format!("虚数 {}i", value),
+            TokenKind::Identifier(name) => format!("标识符 {}", name),
+            TokenKind::Plus => "'+'".to_string(),
+            TokenKind::Minus => "'-'".to_string(),
+            TokenKind::Star => "'*'".to_string(),
+            TokenKind::Slash => "'/'".to_string(),
+            TokenKind::Caret => "'^'".to_string(),
+            TokenKind::DotStar => "'.*'".to_string(),
+            TokenKind::Apostrophe => "'''".to_string(),
+            TokenKind::LeftParen => "'('".to_string(),
+            TokenKind::RightParen => "')'".to_string(),
+            TokenKind::LeftBracket => "'['".to_string(),
+            TokenKind::RightBracket => "']'".to_string(),
+            TokenKind::Comma => "','".to_string(),
+            TokenKind::Semicolon => "';'".to_string(),
+            TokenKind::Equals => "'='".to_string(),
+            TokenKind::Newline => "换行".to_string(),
+            TokenKind::End => "结尾".to_string(),
+        }
+    }
+}
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>, ExpressionError> {
+    let characters: Vec<char> = source.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut position: usize = 0;
+    let mut space_before = false;
+    while position < characters.len() {
+        let c = characters[position];
+        let start = position;
+        if c == '#' {
+            while position < characters.len() && characters[position] != '\n' {
+                position += 1;
+            }
+            continue;
+        }
+        if c.is_whitespace() && c != '\n' {
+            position += 1;
+            space_before = true;
+            continue;
+        }
+        let kind = if c.is_ascii_digit() || (c == '.' && characters.get(position + 1).is_some_and(|next| next.is_ascii_digit())) {
+            while position < characters.len() {
+                let current = characters[position];
+                let exponent_sign = (current == '+' || current == '-') && matches!(characters[position - 1], 'e' | 'E');
+                let exponent = (current == 'e' || current == 'E')
+                    && characters.get(position + 1).is_some_and(|next| next.is_ascii_digit() || *next == '+' || *next == '-');
+                if current.is_ascii_digit() || current == '.' || exponent || exponent_sign {
+                    position += 1;
+                } else {
+                    break;
+                }
+            }
+            let text: String = characters[start..position].iter().collect();
+            let value: f64 = text.parse().map_err(|_| ExpressionError::new(ErrorKind::InvalidNumber(text.clone()), Span::new(start, position)))?;
+            let imaginary = characters.get(position) == Some(&'i')
+                && !characters.get(position + 1).is_some_and(|next| next.is_alphanumeric() || *next == '_');
+            if imaginary {
+                position += 1;
+                TokenKind::Imaginary(value)
+            } else {
+                TokenKind::Number(value)
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            while position < characters.len() && (characters[position].is_alphanumeric() || characters[position] == '_') {
+                position += 1;
+            }
+            TokenKind::Identifier(characters[start..position].iter().collect())
+        } else {
+            position += 1;
+            match c {
+                '+' => TokenKind::Plus,
+                '-' => TokenKind::Minus,
+                '*' => TokenKind::Star,
+                '/' => TokenKind::Slash,
+                '^' => TokenKind::Caret,
+                '\'' => TokenKind::Apostrophe,
+                '(' => TokenKind::LeftParen,
+                ')' => TokenKind::RightParen,
+                '[' => TokenKind::LeftBracket,
+                ']' => TokenKind::RightBracket,
+                ',' => TokenKind::Comma,
+                ';' => TokenKind::Semicolon,
+                '=' => TokenKind::Equals,
+                '\n' => TokenKind::Newline,
+                '.' if characters.get(position) == Some(&'*') => {
+                    position += 1;
+                    TokenKind::DotStar
+                }
+                _ => return Err(ExpressionError::new(ErrorKind::UnexpectedCharacter(c), Span::new(start, position))),
+            }
+        };
+        tokens.push(Token { kind, span: Span::new(start, position), space_before });
+        space_before = false;
+    }
+    tokens.push(Token { kind: TokenKind::End, span: Span::new(characters.len(), characters.len()), space_before });
+    return Ok(tokens);
+}
@@ -0,0 +1,99 @@
+pub mod ast;
+mod evaluator;
+pub mod lexer;
+pub mod parser;
+
+use std::fmt;
+
+pub use ast::Statement;
+pub use evaluator::{Interpreter, Value};
+pub use parser::parse_program;
+
+// 源码中的字符区间 [start, end)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+
+    pub fn merge(&self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ErrorKind {
+    UnexpectedCharacter(char),
+    UnexpectedToken { expected: String, found: String },
+    UnexpectedEnd,
+    InvalidNumber(String),
+    UndefinedVariable(String),
+    UnknownFunction(String),
+    ArgumentCount { function: String, expected: usize, found: usize },
+    TypeMismatch { operation: String, lhs: String, rhs: String },
+    DimensionMismatch { operation: String, lhs: (usize, usize), rhs: (usize, usize) },
+    NotSquare { operation: String, rows: usize, cols: usize },
+    SingularMatrix,
+    DivisionByZero,
+    InvalidArgument(String),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExpressionError {
+    pub kind: ErrorKind,
+    pub span: Span,
+}
+
+impl ExpressionError {
+    pub fn new(kind: ErrorKind, span: Span) -> ExpressionError {
+        ExpressionError { kind, span }
+    }
+
+    // 输出出错的那一行并在下方用 ^ 标出区间
+    pub fn render(&self, source: &str) -> String {
+        let characters: Vec<char> = source.chars().collect();
+        let start = self.span.start.min(characters.len());
+        let line_start = characters[..start].iter().rposition(|c| *c == '\n').map(|index| index + 1).unwrap_or(0);
+        let line_end = characters[start..].iter().position(|c| *c == '\n').map(|index| index + start).unwrap_or(characters.len());
+        let line_number = characters[..line_start].iter().filter(|c| **c == '\n').count() + 1;
+        let line: String = characters[line_start..line_end].iter().collect();
+        let underline_length = (self.span.end.min(line_end).max(start + 1)) - start;
+        return format!("第 {} 行: {}\n  {}\n  {}{}", line_number, self.kind, line,
+            " ".repeat(start - line_start), "^".repeat(underline_length));
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedCharacter(c) => write!(f, "无法识别的字符 '{}'", c),
+            ErrorKind::UnexpectedToken { expected, found } => write!(f, "应为 {}，实际为 {}", expected, found),
+            ErrorKind::UnexpectedEnd => write!(f, "表达式不完整"),
+            ErrorKind::InvalidNumber(text) => write!(f, "无法解析数字 {}", text),
+            ErrorKind::UndefinedVariable(name) => write!(f, "未定义的变量 {}", name),
+            ErrorKind::UnknownFunction(name) => write!(f, "未知函数 {}", name),
+            ErrorKind::ArgumentCount { function, expected, found } =>
+                write!(f, "函数 {} 需要 {} 个参数，实际为 {} 个", function, expected, found),
+            ErrorKind::TypeMismatch { operation, lhs, rhs } if rhs.is_empty() =>
+                write!(f, "{} 不支持 {}", operation, lhs),
+            ErrorKind::TypeMismatch { operation, lhs, rhs } => write!(f, "{} 不支持 {} 与 {}", operation, lhs, rhs),
+            ErrorKind::DimensionMismatch { operation, lhs, rhs } =>
+                write!(f, "{} 的矩阵大小不匹配: {}x{} 与 {}x{}", operation, lhs.0, lhs.1, rhs.0, rhs.1),
+            ErrorKind::NotSquare { operation, rows, cols } => write!(f, "{} 需要方阵，实际为 {}x{}", operation, rows, cols),
+            ErrorKind::SingularMatrix => write!(f, "矩阵奇异"),
+            ErrorKind::DivisionByZero => write!(f, "除数为零"),
+            ErrorKind::InvalidArgument(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (位置 {}..{})", self.kind, self.span.start, self.span.end)
+    }
+}
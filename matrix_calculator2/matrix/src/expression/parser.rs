@@ -0,0 +1,236 @@
+use super::ast::{BinaryOperator, Expr, ExprKind, Statement};
+use super::lexer::{tokenize, Token, TokenKind};
+use super::{ErrorKind, ExpressionError, Span};
+
+// 绑定力：加减 < 乘除 < 负号 < 数字与变量的隐式乘法 < 乘方 < 转置
+const ADDITIVE: u8 = 10;
+const MULTIPLICATIVE: u8 = 20;
+const PREFIX: u8 = 25;
+const IMPLICIT: u8 = 30;
+const POWER: u8 = 40;
+const POSTFIX: u8 = 50;
+
+pub fn parse_statement(source: &str) -> Result<Statement, ExpressionError> {
+    let mut statements = parse_program(source)?;
+    return match statements.len() {
+        1 => Ok(statements.remove(0)),
+        0 => Err(ExpressionError::new(ErrorKind::UnexpectedEnd, Span::new(0, source.chars().count()))),
+        _ => Err(ExpressionError::new(ErrorKind::InvalidArgument("只能包含一条语句".to_string()), Span::new(0, source.chars().count()))),
+    }
+}
+
+// 语句之间用换行或分号分隔
+pub fn parse_program(source: &str) -> Result<Vec<Statement>, ExpressionError> {
+    let mut parser = Parser { tokens: tokenize(source)?, position: 0 };
+    let mut statements: Vec<Statement> = Vec::new();
+    loop {
+        while matches!(parser.peek().kind, TokenKind::Newline | TokenKind::Semicolon) {
+            parser.position += 1;
+        }
+        if parser.peek().kind == TokenKind::End {
+            return Ok(statements);
+        }
+        statements.push(parser.statement()?);
+        match parser.peek().kind {
+            TokenKind::Newline | TokenKind::Semicolon | TokenKind::End => {}
+            _ => return Err(parser.unexpected("语句结尾")),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn statement(&mut self) -> Result<Statement, ExpressionError> {
+        if let TokenKind::Identifier(name) = &self.peek().kind {
+            if self.tokens[self.position + 1].kind == TokenKind::Equals {
+                let name = name.clone();
+                self.position += 2;
+                let value = self.expression(0, false)?;
+                return Ok(Statement::Assign { name, value });
+            }
+        }
+        return Ok(Statement::Expression(self.expression(0, false)?));
+    }
+
+    fn expression(&mut self, min_power: u8, in_matrix: bool) -> Result<Expr, ExpressionError> {
+        let mut lhs = self.prefix(in_matrix)?;
+        loop {
+            let token = self.peek().clone();
+            // 矩阵中 "1 -2" 是两个元素，"1 - 2" 和 "1-2" 才是减法
+            if in_matrix && matches!(token.kind, TokenKind::Plus | TokenKind::Minus)
+                && token.space_before && !self.tokens[self.position + 1].space_before {
+                return Ok(lhs);
+            }
+            let operator = match token.kind {
+                TokenKind::Plus => BinaryOperator::Add,
+                TokenKind::Minus => BinaryOperator::Subtract,
+                TokenKind::Star => BinaryOperator::Multiply,
+                TokenKind::Slash => BinaryOperator::Divide,
+                TokenKind::DotStar => BinaryOperator::Hadamard,
+                TokenKind::Caret => BinaryOperator::Power,
+                TokenKind::Apostrophe => {
+                    if POSTFIX < min_power {
+                        return Ok(lhs);
+                    }
+                    self.position += 1;
+                    let span = lhs.span.merge(token.span);
+                    lhs = Expr { kind: ExprKind::Transpose(Box::new(lhs)), span };
+                    continue;
+                }
+                _ => return Ok(lhs),
+            };
+            let (left_power, right_power) = match operator {
+                BinaryOperator::Add | BinaryOperator::Subtract => (ADDITIVE, ADDITIVE + 1),
+                BinaryOperator::Power => (POWER + 1, POWER),
+                _ => (MULTIPLICATIVE, MULTIPLICATIVE + 1),
+            };
+            if left_power < min_power {
+                return Ok(lhs);
+            }
+            self.position += 1;
+            let rhs = self.expression(right_power, in_matrix)?;
+            let span = lhs.span.merge(rhs.span);
+            lhs = Expr { kind: ExprKind::Binary { operator, lhs: Box::new(lhs), rhs: Box::new(rhs) }, span };
+        }
+    }
+
+    fn prefix(&mut self, in_matrix: bool) -> Result<Expr, ExpressionError> {
+        let token = self.advance();
+        let expr = match token.kind {
+            TokenKind::Number(value) => {
+                let number = Expr { kind: ExprKind::Number(value), span: token.span };
+                // 2x^3、3(x+1) 这样紧挨着的写法视为乘法
+                let next = self.peek();
+                if !next.space_before && matches!(next.kind, TokenKind::Identifier(_) | TokenKind::LeftParen) {
+                    let rhs = self.expression(IMPLICIT, in_matrix)?;
+                    let span = number.span.merge(rhs.span);
+                    return Ok(Expr { kind: ExprKind::Binary { operator: BinaryOperator::Multiply, lhs: Box::new(number), rhs: Box::new(rhs) }, span });
+                }
+                number
+            }
+            TokenKind::Imaginary(value) => Expr { kind: ExprKind::Imaginary(value), span: token.span },
+            TokenKind::Minus => {
+                let operand = self.expression(PREFIX, in_matrix)?;
+                let span = token.span.merge(operand.span);
+                Expr { kind: ExprKind::Negate(Box::new(operand)), span }
+            }
+            TokenKind::Plus => self.expression(PREFIX, in_matrix)?,
+            TokenKind::LeftParen => {
+                let inner = self.expression(0, false)?;
+                let close = self.expect(TokenKind::RightParen, "')'")?;
+                Expr { kind: inner.kind, span: token.span.merge(close.span) }
+            }
+            TokenKind::LeftBracket => self.matrix_literal(token.span)?,
+            TokenKind::Identifier(name) => {
+                if self.peek().kind == TokenKind::LeftParen && !self.peek().space_before {
+                    self.position += 1;
+                    let mut arguments: Vec<Expr> = Vec::new();
+                    if self.peek().kind != TokenKind::RightParen {
+                        loop {
+                            arguments.push(self.expression(0, false)?);
+                            if self.peek().kind != TokenKind::Comma {
+                                break;
+                            }
+                            self.position += 1;
+                        }
+                    }
+                    let close = self.expect(TokenKind::RightParen, "')'")?;
+                    Expr { kind: ExprKind::Call { function: name, arguments }, span: token.span.merge(close.span) }
+                } else {
+                    Expr { kind: ExprKind::Variable(name), span: token.span }
+                }
+            }
+            TokenKind::End => return Err(ExpressionError::new(ErrorKind::UnexpectedEnd, token.span)),
+            other => return Err(ExpressionError::new(
+                ErrorKind::UnexpectedToken { expected: "表达式".to_string(), found: other.describe() }, token.span)),
+        };
+        return Ok(expr);
+    }
+
+    // [1 2; 3 4]、[1, 2; 3, 4] 或 [[1, 2], [3, 4]]
+    fn matrix_literal(&mut self, open: Span) -> Result<Expr, ExpressionError> {
+        self.skip_newlines();
+        let mut rows: Vec<Vec<Expr>> = Vec::new();
+        if self.peek().kind == TokenKind::LeftBracket {
+            loop {
+                self.expect(TokenKind::LeftBracket, "'['")?;
+                let mut row: Vec<Expr> = Vec::new();
+                loop {
+                    row.push(self.expression(0, false)?);
+                    if self.peek().kind != TokenKind::Comma {
+                        break;
+                    }
+                    self.position += 1;
+                }
+                self.expect(TokenKind::RightBracket, "']'")?;
+                rows.push(row);
+                self.skip_newlines();
+                if self.peek().kind != TokenKind::Comma {
+                    break;
+                }
+                self.position += 1;
+                self.skip_newlines();
+            }
+        } else {
+            let mut row: Vec<Expr> = Vec::new();
+            loop {
+                match self.peek().kind {
+                    TokenKind::RightBracket => break,
+                    TokenKind::Semicolon | TokenKind::Newline => {
+                        self.position += 1;
+                        if !row.is_empty() {
+                            rows.push(row);
+                            row = Vec::new();
+                        }
+                    }
+                    TokenKind::Comma => self.position += 1,
+                    _ => row.push(self.expression(0, true)?),
+                }
+            }
+            if !row.is_empty() {
+                rows.push(row);
+            }
+        }
+        let close = self.expect(TokenKind::RightBracket, "']'")?;
+        return Ok(Expr { kind: ExprKind::Matrix(rows), span: open.merge(close.span) });
+    }
+
+    fn skip_newlines(&mut self) {
+        while self.peek().kind == TokenKind::Newline {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        return &self.tokens[self.position];
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.position].clone();
+        if token.kind != TokenKind::End {
+            self.position += 1;
+        }
+        return token;
+    }
+
+    fn expect(&mut self, kind: TokenKind, description: &str) -> Result<Token, ExpressionError> {
+        if self.peek().kind == kind {
+            return Ok(self.advance());
+        }
+        return Err(self.unexpected(description));
+    }
+
+    fn unexpected(&self, expected: &str) -> ExpressionError {
+        let token = self.peek();
+        let kind = if token.kind == TokenKind::End {
+            ErrorKind::UnexpectedEnd
+        } else {
+            ErrorKind::UnexpectedToken { expected: expected.to_string(), found: token.kind.describe() }
+        };
+        return ExpressionError::new(kind, token.span);
+    }
+}
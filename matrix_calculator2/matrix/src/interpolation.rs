@@ -0,0 +1,133 @@
+use crate::complex::Complex;
+use crate::matrix::Matrix;
+use crate::polynomial::Polynomial;
+
+// 插值多项式的系数保留的小数位数，去掉除法留下的舍入误差，避免本该为零的高次项
+const INTERPOLATION_SCALE: i64 = 50;
+
+// 最小二乘拟合的结果与拟合优度
+#[derive(Clone)]
+pub struct PolynomialFit {
+    pub polynomial: Polynomial,
+    // 每个数据点的残差 y_i - p(x_i)
+    pub residuals: Vec<f64>,
+    // 残差平方和
+    pub residual_sum_of_squares: f64,
+    // 决定系数 1 - SSE/SST，所有 y 都相同时为 1
+    pub r_squared: f64,
+    // 按自由度修正的决定系数，数据点个数恰好等于系数个数时为 None
+    pub adjusted_r_squared: Option<f64>,
+    // 残差的标准误差 sqrt(SSE / (n - degree - 1))，自由度为零时为 None
+    pub standard_error: Option<f64>,
+}
+
+impl Polynomial {
+    // Lagrange 插值：Σ y_i Π_{j≠i} (x - x_j)/(x_i - x_j)。横坐标有重复时返回 None
+    pub fn interpolate_lagrange(points: &[(f64, f64)]) -> Option<Polynomial> {
+        let nodes = nodes(points)?;
+        let mut result = Polynomial::zero();
+        for (i, (x_i, y_i)) in nodes.iter().enumerate() {
+            let mut basis = Polynomial::constant(y_i);
+            for (j, (x_j, _)) in nodes.iter().enumerate() {
+                if i != j {
+                    basis = &(&basis * &linear(x_j)) / &(x_i - x_j);
+                }
+            }
+            result = &result + &basis;
+        }
+        return Some(result.round_coefficients(INTERPOLATION_SCALE));
+    }
+
+    // Newton 插值：先算差商 f[x_0], f[x_0, x_1], ...，再按嵌套形式
+    // f[x_0] + (x - x_0)(f[x_0, x_1] + (x - x_1)(...)) 展开
+    pub fn interpolate_newton(points: &[(f64, f64)]) -> Option<Polynomial> {
+        let nodes = nodes(points)?;
+        if nodes.is_empty() {
+            return Some(Polynomial::zero());
+        }
+        let mut differences: Vec<Complex> = nodes.iter().map(|(_, y)| y.clone()).collect();
+        for level in 1..nodes.len() {
+            for index in (level..nodes.len()).rev() {
+                differences[index] = &(&differences[index] - &differences[index - 1]) / &(&nodes[index].0 - &nodes[index - level].0);
+            }
+        }
+        let mut result = Polynomial::constant(differences.last().unwrap());
+        for index in (0..nodes.len() - 1).rev() {
+            result = &(&result * &linear(&nodes[index].0)) + &Polynomial::constant(&differences[index]);
+        }
+        return Some(result.round_coefficients(INTERPOLATION_SCALE));
+    }
+
+    // 重心插值：权重 w_i = 1/Π_{j≠i}(x_i - x_j)，p(x) = ℓ(x) Σ w_i y_i/(x - x_i)，ℓ(x) = Π (x - x_j)。
+    // ℓ(x)/(x - x_i) 用综合除法得到，不需要对每个基函数重新连乘
+    pub fn interpolate_barycentric(points: &[(f64, f64)]) -> Option<Polynomial> {
+        let nodes = nodes(points)?;
+        let one = Complex::init(1.0, 0.0);
+        let mut nodal = Polynomial::constant(&one);
+        for (x, _) in &nodes {
+            nodal = &nodal * &linear(x);
+        }
+        let mut result = Polynomial::zero();
+        for (i, (x_i, y_i)) in nodes.iter().enumerate() {
+            let mut weight = one.clone();
+            for (j, (x_j, _)) in nodes.iter().enumerate() {
+                if i != j {
+                    weight = &weight / &(x_i - x_j);
+                }
+            }
+            result = &result + &(&nodal.deflate(x_i) * &(&weight * y_i));
+        }
+        return Some(result.round_coefficients(INTERPOLATION_SCALE));
+    }
+
+    // 最小二乘拟合 degree 次多项式：对 Vandermonde 矩阵做带列主元的 QR 分解求解。
+    // 数据点少于 degree + 1 个或者互不相同的横坐标不够多时返回 None
+    pub fn fit(points: &[(f64, f64)], degree: usize) -> Option<PolynomialFit> {
+        if points.len() <= degree {
+            return None;
+        }
+        let xs: Vec<f64> = points.iter().map(|(x, _)| *x).collect();
+        let ys: Matrix<f64> = Matrix::init(points.len(), 1, points.iter().map(|(_, y)| *y).collect());
+        let vandermonde = Matrix::vandermonde(&xs, degree);
+        let qr = Matrix::qr(&vandermonde);
+        let tolerance = f64::EPSILON * points.len() as f64 * qr.r.get(0, 0).abs();
+        let coefficients = qr.least_squares(&ys, tolerance)?;
+        let fitted = &vandermonde * &coefficients;
+        let residuals: Vec<f64> = (0..points.len()).map(|row| ys.get(row, 0) - fitted.get(row, 0)).collect();
+        let residual_sum_of_squares: f64 = residuals.iter().map(|residual| residual * residual).sum();
+        let mean = points.iter().map(|(_, y)| y).sum::<f64>() / points.len() as f64;
+        let total_sum_of_squares: f64 = points.iter().map(|(_, y)| (y - mean).powi(2)).sum();
+        let r_squared = if total_sum_of_squares == 0.0 { 1.0 } else { 1.0 - residual_sum_of_squares / total_sum_of_squares };
+        let degrees_of_freedom = points.len() - degree - 1;
+        let (adjusted_r_squared, standard_error) = if degrees_of_freedom == 0 {
+            (None, None)
+        } else {
+            let adjusted = 1.0 - (1.0 - r_squared) * (points.len() - 1) as f64 / degrees_of_freedom as f64;
+            (Some(adjusted), Some((residual_sum_of_squares / degrees_of_freedom as f64).sqrt()))
+        };
+        let coefficients: Vec<f64> = (0..=degree).map(|index| *coefficients.get(index, 0)).collect();
+        return Some(PolynomialFit {
+            polynomial: Polynomial::from_f64(&coefficients),
+            residuals,
+            residual_sum_of_squares,
+            r_squared,
+            adjusted_r_squared,
+            standard_error,
+        });
+    }
+}
+
+// 转换为复数，横坐标有重复时返回 None
+fn nodes(points: &[(f64, f64)]) -> Option<Vec<(Complex, Complex)>> {
+    for (index, (x, _)) in points.iter().enumerate() {
+        if points[..index].iter().any(|(other, _)| other == x) {
+            return None;
+        }
+    }
+    return Some(points.iter().map(|(x, y)| (Complex::init(*x, 0.0), Complex::init(*y, 0.0))).collect());
+}
+
+// x - root
+fn linear(root: &Complex) -> Polynomial {
+    return Polynomial::new(vec![&Complex::new() - root, Complex::init(1.0, 0.0)]);
+}
@@ -0,0 +1,50 @@
+// 矩阵、复数与多项式方程的计算库。
+// matrix 与其格式化、解析、读写、矩阵函数始终可用；complex、polynomial、equation 以及依赖它们的
+// 表达式语言、交互模式和命令行需要 bigdecimal 特性，随机矩阵需要 rand 特性
+#![allow(clippy::needless_return)]
+
+pub mod matrix;
+pub mod matrix_format;
+pub mod matrix_function;
+pub mod matrix_io;
+pub mod matrix_parser;
+pub mod decomposition;
+pub mod permutation;
+pub mod elementary;
+pub mod elimination_trace;
+pub mod smatrix;
+#[cfg(feature = "bigdecimal")]
+pub mod complex;
+#[cfg(feature = "bigdecimal")]
+pub mod equation;
+#[cfg(feature = "bigdecimal")]
+pub mod polynomial;
+#[cfg(feature = "bigdecimal")]
+pub mod polynomial_parser;
+#[cfg(feature = "bigdecimal")]
+pub mod root_finder;
+#[cfg(feature = "bigdecimal")]
+pub mod closed_form;
+#[cfg(feature = "bigdecimal")]
+pub mod interpolation;
+#[cfg(feature = "bigdecimal")]
+pub mod real_roots;
+#[cfg(feature = "bigdecimal")]
+pub mod expression;
+#[cfg(feature = "bigdecimal")]
+pub mod repl;
+#[cfg(feature = "bigdecimal")]
+pub mod cli;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+mod test;
+
+pub use crate::matrix::Matrix;
+pub use crate::smatrix::SMatrix;
+pub use crate::permutation::Permutation;
+#[cfg(feature = "bigdecimal")]
+pub use crate::complex::Complex;
+#[cfg(feature = "bigdecimal")]
+pub use crate::equation::EquationWithDegrees;
+#[cfg(feature = "bigdecimal")]
+pub use crate::polynomial::Polynomial;
@@ -1,32 +1,9 @@
-mod matrix;
-mod complex;
-mod equation;
-mod test;
-
-use std::str::FromStr;
-use bigdecimal::BigDecimal;
-use complex::Complex;
-use crate::equation::EquationWithDegrees;
-
+// 带参数时作为批处理命令行工具运行，否则进入交互模式
 fn main() {
-    let parameters = vec![
-        Complex::from_big_decimal(&BigDecimal::from(-1), &BigDecimal::from(0)), // 常数项 -1
-        Complex::from_big_decimal(&BigDecimal::from(-2), &BigDecimal::from(0)),  // x 的系数 2
-        Complex::from_big_decimal(&BigDecimal::from(6), &BigDecimal::from(0)),  // x^2 的系数 3
-        Complex::from_big_decimal(&BigDecimal::from(2), &BigDecimal::from(0)),  // x^3 的系数 2
-    ];
-    let equation = EquationWithDegrees::new(parameters);
-
-    // 设置误差阈值
-    let threshold = BigDecimal::from_str("0.0001").unwrap();
-
-    // 求解
-    let roots = equation.solve(&threshold);
-
-    // 打印结果
-    println!("2x³+5x²-2x-1=0 的解是:");
-    for root_index in 0..roots.len() {
-        let root = roots.get(root_index).unwrap();
-        println!("x_{} = {} + {}i", root_index+1, root.real.round(10), root.imaginary.round(10));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        matrix::repl::run();
+        return;
     }
+    std::process::exit(matrix::cli::main(&args));
 }
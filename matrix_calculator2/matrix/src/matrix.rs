@@ -1,12 +1,17 @@
 use std::fs::OpenOptions;
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "rand")]
 use rand::{Rng, SeedableRng};
+#[cfg(feature = "rand")]
 use rand::rngs::StdRng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use num_traits::One;
-use std::io::Write;
-use super::complex::Complex;
+use super::elementary::{ElementaryOperation, EliminationObserver};
+use super::permutation::Permutation;
+use super::matrix_format::{Alignment, MatrixFormatter};
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Matrix<T> {
     rows: usize,
     cols: usize,
@@ -38,52 +43,27 @@ impl<T> Matrix<T> where T:
     }
 
     pub fn print_matrix(matrix: &Matrix<T>){
-        let width = 10;
-        print!("┌");
-        print!("{}", " ".repeat((width+1) * matrix.get_cols()));
-        print!("┐");
-        println!();
-        for row in 0..matrix.get_rows() {
-            print!("│");
-            for col in 0..matrix.get_cols() {
-                let element: String = format!("{:.3},", matrix.get(row, col));
-                print!("{:^width$} ", element, width = width);
-            }
-            print!("│");
-            println!();
-        }
-        print!("└");
-        print!("{}", " ".repeat((width+1) * matrix.get_cols()));
-        print!("┘");
-        println!();
+        let formatter = MatrixFormatter {
+            precision: Some(3),
+            width: Some(10),
+            alignment: Alignment::Center,
+            ..MatrixFormatter::default()
+        };
+        print!("{}", formatter.format(matrix));
     }
 
     pub fn write_to_file(matrix: &Matrix<T>, file_name: &String, word_size:usize, decimal_space:usize) -> std::io::Result<()> {
         let mut file = OpenOptions::new()
-            .write(true)
             .create(true)
             .append(true)
             .open(file_name)?;
-    
-        let width = word_size;
-        write!(file, "┌")?;
-        write!(file, "{}", " ".repeat((width+1) * matrix.get_cols()))?;
-        write!(file, "┐\n")?;
-
-        for row in 0..matrix.get_rows() {
-            write!(file, "│")?;
-            for col in 0..matrix.get_cols() {
-                let element = format!("{:.precision$},", matrix.get(row, col).to_string(),precision=decimal_space);
-                write!(file, "{:^width$} ", element, width = width)?;
-            }
-            write!(file, "│\n")?;
-        }
-
-        write!(file, "└")?;
-        write!(file, "{}", " ".repeat((width+1) * matrix.get_cols()))?;
-        write!(file, "┘\n")?;
-
-        Ok(())
+        let formatter = MatrixFormatter {
+            precision: Some(decimal_space),
+            width: Some(word_size),
+            alignment: Alignment::Center,
+            ..MatrixFormatter::default()
+        };
+        return formatter.write(matrix, &mut file);
     }
 
     pub fn set(&mut self, row: usize, col: usize, value: T) {
@@ -116,65 +96,75 @@ impl<T> Matrix<T> where T:
         return result;
     }
 
+    // 消元得到的上三角矩阵对角元之积，符号由行置换的奇偶性决定
     pub fn determinant(target:&Matrix<T>) -> T{
-        let (upper_triangular_matrix,swap_ops) = Matrix::get_upper_triangular_matrix(target);
+        let (upper_triangular_matrix, permutation, _) = Matrix::eliminate_to_upper_triangular(target, None);
         let mut result: T = *upper_triangular_matrix.get(0, 0);
         for index in 1..upper_triangular_matrix.get_cols().min(upper_triangular_matrix.get_rows()) {
             result = result * *upper_triangular_matrix.get(index, index);
         }
-        return if swap_ops % 2 == 0 {
+        return if permutation.sign() > 0 {
             result
         } else {
             T::default() - result
         }
     }
 
+    // 返回上三角矩阵和行交换的次数
     pub fn get_upper_triangular_matrix(target:&Matrix<T>) -> (Matrix<T>, i64){
+        let (result_matrix, _, swap_ops) = Matrix::eliminate_to_upper_triangular(target, None);
+        return (result_matrix, swap_ops);
+    }
+
+    // 部分主元消元：每列选绝对值最大的元素作为主元，整列为零时跳过这一列。
+    // 返回上三角矩阵、累计的行置换以及行交换的次数
+    pub(crate) fn eliminate_to_upper_triangular(target:&Matrix<T>, mut observer:Option<&mut dyn EliminationObserver<T>>) -> (Matrix<T>, Permutation, i64){
         let mut result_matrix: Matrix<T> = Matrix::init(target.get_rows(), target.get_cols(), target.data.clone());
+        let mut permutation = Permutation::identity(target.get_rows());
         let mut swap_ops: i64 = 0;
-        for col_index in 0 .. result_matrix.get_cols(){
-            let mut pivot : T = *result_matrix.get(col_index, col_index);
+        for col_index in 0 .. result_matrix.get_cols().min(result_matrix.get_rows()){
             let mut pivot_index : usize = col_index;
             for row_index in col_index + 1 .. result_matrix.get_rows(){
-                let item = *result_matrix.get(row_index, col_index);
-                if item > pivot || T::default() - item > pivot {
-                   pivot = item; 
-                   pivot_index = row_index;
+                if Self::abs_of(*result_matrix.get(row_index, col_index)) > Self::abs_of(*result_matrix.get(pivot_index, col_index)) {
+                    pivot_index = row_index;
                 }
             }
+            let pivot : T = *result_matrix.get(pivot_index, col_index);
+            if let Some(observer) = observer.as_deref_mut() {
+                observer.pivot(col_index, pivot_index, pivot);
+            }
+            if pivot == T::default() {
+                continue;
+            }
 
             if pivot_index != col_index{
                 swap_ops += 1;
-                result_matrix.swap_rows(pivot_index, col_index);
+                permutation.swap(pivot_index, col_index);
+                let operation = ElementaryOperation::SwapRows(pivot_index, col_index);
+                operation.apply(&mut result_matrix);
+                if let Some(observer) = observer.as_deref_mut() {
+                    observer.operation(&operation, &result_matrix);
+                }
             }
 
             for row_index in col_index + 1 .. result_matrix.get_rows() {
                 let first_item: T = *result_matrix.get(row_index, col_index);
                 let ratio: <T as Sub<T>>::Output = T::default() - (first_item / pivot);
-                for sub_col_index in col_index..result_matrix.get_cols() {
-                    result_matrix.set(row_index, sub_col_index, 
-                        *result_matrix.get(row_index, sub_col_index) + 
-                        ratio * *result_matrix.get(col_index, sub_col_index));
+                let operation = ElementaryOperation::AddRowMultiple { target: row_index, source: col_index, factor: ratio };
+                operation.apply(&mut result_matrix);
+                if let Some(observer) = observer.as_deref_mut() {
+                    observer.operation(&operation, &result_matrix);
                 }
             }
         }
-        return (result_matrix, swap_ops);
-    }
-
-    fn swap_rows(&mut self, from_row: usize, to_row: usize){
-        let mut cache:T;
-        for col in 0 .. self.get_cols() {
-            cache = *self.get(from_row, col);
-            self.set(from_row, col, *self.get(to_row, col));
-            self.set(to_row, col, cache);
-        }
+        return (result_matrix, permutation, swap_ops);
     }
 
     pub fn inverse(target:&Matrix<T>)-> Matrix<T>{
         let mut result_matrix = Matrix::get_matrix_of_minors(target);
         result_matrix = Matrix::get_matrix_of_cofactors(&result_matrix);
         result_matrix = Matrix::transpose(&result_matrix);
-        result_matrix = &result_matrix * (T::one() / Matrix::determinant(&target));
+        result_matrix = &result_matrix * (T::one() / Matrix::determinant(target));
         return result_matrix;
     }
 
@@ -228,6 +218,256 @@ impl<T> Matrix<T> where T:
         return result_matrix;
     }
 
+    pub fn identity(size:usize) -> Matrix<T>{
+        let mut result_matrix: Matrix<T> = Matrix::new(size, size);
+        for index in 0..size {
+            result_matrix.set(index, index, T::one());
+        }
+        return result_matrix;
+    }
+
+    // 第 i 行为 1, x_i, x_i², ..., x_i^degree，列的顺序与多项式系数从低到高的顺序一致
+    pub fn vandermonde(points:&[T], degree:usize) -> Matrix<T>{
+        let mut result_matrix: Matrix<T> = Matrix::new(points.len(), degree + 1);
+        for (row, point) in points.iter().enumerate() {
+            let mut power = T::one();
+            for col in 0..=degree {
+                result_matrix.set(row, col, power);
+                power = power * *point;
+            }
+        }
+        return result_matrix;
+    }
+
+    // 快速幂：反复平方，只依赖现有的矩阵乘法
+    pub fn pow(target:&Matrix<T>, exponent:u32) -> Matrix<T>{
+        assert_eq!(target.get_rows(), target.get_cols(), "只有方阵可以求幂");
+        let mut result_matrix: Matrix<T> = Matrix::identity(target.get_rows());
+        let mut base: Matrix<T> = target.clone();
+        let mut remaining = exponent;
+        while remaining > 0 {
+            if remaining % 2 == 1 {
+                result_matrix = &result_matrix * &base;
+            }
+            remaining /= 2;
+            if remaining > 0 {
+                base = &base * &base;
+            }
+        }
+        return result_matrix;
+    }
+
+    // 列主元高斯消元求解 AX = B，矩阵奇异时返回 None
+    pub fn solve(lhs:&Matrix<T>, rhs:&Matrix<T>) -> Option<Matrix<T>>{
+        assert_eq!(lhs.get_rows(), lhs.get_cols(), "系数矩阵必须是方阵");
+        assert_eq!(lhs.get_rows(), rhs.get_rows(), "矩阵大小不匹配");
+        let size = lhs.get_rows();
+        let mut matrix_a: Matrix<T> = lhs.clone();
+        let mut matrix_b: Matrix<T> = rhs.clone();
+        for col_index in 0..size {
+            let mut pivot_index = col_index;
+            let mut pivot_abs = Self::abs_of(*matrix_a.get(col_index, col_index));
+            for row_index in col_index + 1..size {
+                let item_abs = Self::abs_of(*matrix_a.get(row_index, col_index));
+                if item_abs > pivot_abs {
+                    pivot_abs = item_abs;
+                    pivot_index = row_index;
+                }
+            }
+            if pivot_abs == T::default() {
+                return None;
+            }
+            if pivot_index != col_index {
+                matrix_a.swap_rows(pivot_index, col_index);
+                matrix_b.swap_rows(pivot_index, col_index);
+            }
+            let pivot = *matrix_a.get(col_index, col_index);
+            for row_index in col_index + 1..size {
+                let ratio = *matrix_a.get(row_index, col_index) / pivot;
+                for sub_col_index in col_index..size {
+                    matrix_a.set(row_index, sub_col_index,
+                        *matrix_a.get(row_index, sub_col_index) - ratio * *matrix_a.get(col_index, sub_col_index));
+                }
+                for sub_col_index in 0..matrix_b.get_cols() {
+                    matrix_b.set(row_index, sub_col_index,
+                        *matrix_b.get(row_index, sub_col_index) - ratio * *matrix_b.get(col_index, sub_col_index));
+                }
+            }
+        }
+        let mut result_matrix: Matrix<T> = Matrix::new(size, matrix_b.get_cols());
+        for col in 0..matrix_b.get_cols() {
+            for row in (0..size).rev() {
+                let mut sum = *matrix_b.get(row, col);
+                for k in row + 1..size {
+                    sum = sum - *matrix_a.get(row, k) * *result_matrix.get(k, col);
+                }
+                result_matrix.set(row, col, sum / *matrix_a.get(row, row));
+            }
+        }
+        return Some(result_matrix);
+    }
+
+    pub fn kronecker(lhs:&Matrix<T>, rhs:&Matrix<T>) -> Matrix<T>{
+        let mut result_matrix: Matrix<T> = Matrix::new(lhs.get_rows() * rhs.get_rows(), lhs.get_cols() * rhs.get_cols());
+        for lhs_row in 0..lhs.get_rows() {
+            for lhs_col in 0..lhs.get_cols() {
+                let factor = *lhs.get(lhs_row, lhs_col);
+                for rhs_row in 0..rhs.get_rows() {
+                    for rhs_col in 0..rhs.get_cols() {
+                        result_matrix.set(lhs_row * rhs.get_rows() + rhs_row, lhs_col * rhs.get_cols() + rhs_col,
+                            factor * *rhs.get(rhs_row, rhs_col));
+                    }
+                }
+            }
+        }
+        return result_matrix;
+    }
+
+    pub fn direct_sum(lhs:&Matrix<T>, rhs:&Matrix<T>) -> Matrix<T>{
+        let mut result_matrix: Matrix<T> = Matrix::new(lhs.get_rows() + rhs.get_rows(), lhs.get_cols() + rhs.get_cols());
+        for row in 0..lhs.get_rows() {
+            for col in 0..lhs.get_cols() {
+                result_matrix.set(row, col, *lhs.get(row, col));
+            }
+        }
+        for row in 0..rhs.get_rows() {
+            for col in 0..rhs.get_cols() {
+                result_matrix.set(lhs.get_rows() + row, lhs.get_cols() + col, *rhs.get(row, col));
+            }
+        }
+        return result_matrix;
+    }
+
+    // 按列堆叠成列向量
+    pub fn vec(target:&Matrix<T>) -> Matrix<T>{
+        let mut result_matrix: Matrix<T> = Matrix::new(target.get_rows() * target.get_cols(), 1);
+        for col in 0..target.get_cols() {
+            for row in 0..target.get_rows() {
+                result_matrix.set(col * target.get_rows() + row, 0, *target.get(row, col));
+            }
+        }
+        return result_matrix;
+    }
+
+    pub fn unvec(target:&Matrix<T>, rows:usize) -> Matrix<T>{
+        assert_eq!(target.get_cols(), 1, "只能还原列向量");
+        assert!(rows > 0 && target.get_rows().is_multiple_of(rows), "行数无法整除向量长度");
+        let cols = target.get_rows() / rows;
+        let mut result_matrix: Matrix<T> = Matrix::new(rows, cols);
+        for col in 0..cols {
+            for row in 0..rows {
+                result_matrix.set(row, col, *target.get(col * rows + row, 0));
+            }
+        }
+        return result_matrix;
+    }
+
+    // 交换矩阵 K，满足 K * vec(A) = vec(A^T)，其中 A 为 rows x cols
+    pub fn commutation_matrix(rows:usize, cols:usize) -> Matrix<T>{
+        let mut result_matrix: Matrix<T> = Matrix::new(rows * cols, rows * cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                result_matrix.set(row * cols + col, col * rows + row, T::one());
+            }
+        }
+        return result_matrix;
+    }
+
+    // 求解 Sylvester 方程 AX + XB = C：(I ⊗ A + B^T ⊗ I) vec(X) = vec(C)
+    pub fn sylvester(matrix_a:&Matrix<T>, matrix_b:&Matrix<T>, matrix_c:&Matrix<T>) -> Option<Matrix<T>>{
+        assert_eq!(matrix_a.get_rows(), matrix_a.get_cols(), "A 必须是方阵");
+        assert_eq!(matrix_b.get_rows(), matrix_b.get_cols(), "B 必须是方阵");
+        assert!(matrix_c.get_rows() == matrix_a.get_rows() && matrix_c.get_cols() == matrix_b.get_rows(), "矩阵大小不匹配");
+        let identity_a: Matrix<T> = Matrix::identity(matrix_a.get_rows());
+        let identity_b: Matrix<T> = Matrix::identity(matrix_b.get_rows());
+        let system = &Matrix::kronecker(&identity_b, matrix_a) + &Matrix::kronecker(&Matrix::transpose(matrix_b), &identity_a);
+        let solution = Matrix::solve(&system, &Matrix::vec(matrix_c))?;
+        return Some(Matrix::unvec(&solution, matrix_a.get_rows()));
+    }
+
+    // 连续 Lyapunov 方程 AX + XA^T = C
+    pub fn lyapunov(matrix_a:&Matrix<T>, matrix_c:&Matrix<T>) -> Option<Matrix<T>>{
+        return Matrix::sylvester(matrix_a, &Matrix::transpose(matrix_a), matrix_c);
+    }
+
+    // 行最简形：列主元消元后把每个主元化为 1 并消去其所在列的其他元素
+    pub fn rref(target:&Matrix<T>) -> Matrix<T>{
+        let mut result_matrix: Matrix<T> = target.clone();
+        let mut pivot_row: usize = 0;
+        for col_index in 0..result_matrix.get_cols() {
+            if pivot_row == result_matrix.get_rows() {
+                break;
+            }
+            let mut pivot_index = pivot_row;
+            for row_index in pivot_row + 1..result_matrix.get_rows() {
+                if Self::abs_of(*result_matrix.get(row_index, col_index)) > Self::abs_of(*result_matrix.get(pivot_index, col_index)) {
+                    pivot_index = row_index;
+                }
+            }
+            let pivot = *result_matrix.get(pivot_index, col_index);
+            if pivot == T::default() {
+                continue;
+            }
+            result_matrix.swap_rows(pivot_index, pivot_row);
+            for sub_col_index in 0..result_matrix.get_cols() {
+                result_matrix.set(pivot_row, sub_col_index, *result_matrix.get(pivot_row, sub_col_index) / pivot);
+            }
+            result_matrix.set(pivot_row, col_index, T::one());
+            for row_index in 0..result_matrix.get_rows() {
+                let factor = *result_matrix.get(row_index, col_index);
+                if row_index == pivot_row || factor == T::default() {
+                    continue;
+                }
+                for sub_col_index in 0..result_matrix.get_cols() {
+                    result_matrix.set(row_index, sub_col_index,
+                        *result_matrix.get(row_index, sub_col_index) - factor * *result_matrix.get(pivot_row, sub_col_index));
+                }
+                result_matrix.set(row_index, col_index, T::default());
+            }
+            pivot_row += 1;
+        }
+        return result_matrix;
+    }
+
+    // 大小相同且每个元素之差的绝对值都不超过 tolerance
+    pub fn approx_eq(lhs:&Matrix<T>, rhs:&Matrix<T>, tolerance:T) -> bool{
+        if lhs.get_rows() != rhs.get_rows() || lhs.get_cols() != rhs.get_cols() {
+            return false;
+        }
+        return lhs.data.iter().zip(rhs.data.iter()).all(|(a, b)| Self::abs_of(*a - *b) <= tolerance);
+    }
+
+    pub(crate) fn abs_of(value:T) -> T{
+        return if value < T::default() {
+            T::default() - value
+        } else {
+            value
+        }
+    }
+
+}
+
+#[cfg(feature = "parallel")]
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + Send + Sync {
+    // 与 &lhs * &rhs 结果相同，按行分给 rayon 线程池并行计算
+    pub fn par_multiply(lhs:&Matrix<T>, rhs:&Matrix<T>) -> Matrix<T>{
+        assert_eq!(lhs.get_cols(), rhs.get_rows(), "矩阵大小不匹配");
+        let mut result_matrix: Matrix<T> = Matrix::new(lhs.rows, rhs.cols);
+        if rhs.cols == 0 {
+            return result_matrix;
+        }
+        result_matrix.data.par_chunks_mut(rhs.cols).enumerate().for_each(|(i, row)| {
+            for (j, cell) in row.iter_mut().enumerate() {
+                let mut sum = T::default();
+                for k in 0..lhs.cols {
+                    sum = sum + lhs.data[i * lhs.cols + k] * rhs.data[k * rhs.cols + j];
+                }
+                *cell = sum;
+            }
+        });
+        return result_matrix;
+    }
 }
 
 impl<T> Add for &Matrix<T> where 
@@ -291,6 +531,227 @@ impl<T> Mul<T> for &Matrix<T> where
 }
 
 
+// 左操作数为所有权值时直接在其缓冲区上计算，不再分配新的矩阵
+impl<T> AddAssign<&Matrix<T>> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    fn add_assign(&mut self, other: &Matrix<T>) {
+        assert!(self.get_cols() == other.get_cols() && self.get_rows() == other.get_rows(), "矩阵大小不匹配");
+        for (element, value) in self.data.iter_mut().zip(other.data.iter()) {
+            *element = *element + *value;
+        }
+    }
+}
+
+impl<T> AddAssign for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    fn add_assign(&mut self, other: Matrix<T>) {
+        *self += &other;
+    }
+}
+
+impl<T> SubAssign<&Matrix<T>> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    fn sub_assign(&mut self, other: &Matrix<T>) {
+        assert!(self.get_cols() == other.get_cols() && self.get_rows() == other.get_rows(), "矩阵大小不匹配");
+        for (element, value) in self.data.iter_mut().zip(other.data.iter()) {
+            *element = *element - *value;
+        }
+    }
+}
+
+impl<T> SubAssign for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    fn sub_assign(&mut self, other: Matrix<T>) {
+        *self -= &other;
+    }
+}
+
+impl<T> MulAssign<T> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    fn mul_assign(&mut self, rhs: T) {
+        for element in self.data.iter_mut() {
+            *element = *element * rhs;
+        }
+    }
+}
+
+impl<T> DivAssign<T> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    fn div_assign(&mut self, rhs: T) {
+        for element in self.data.iter_mut() {
+            *element = *element / rhs;
+        }
+    }
+}
+
+impl<T> Add<&Matrix<T>> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn add(mut self, other: &Matrix<T>) -> Self::Output {
+        self += other;
+        return self;
+    }
+}
+
+impl<T> Add for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn add(self, other: Matrix<T>) -> Self::Output {
+        return self + &other;
+    }
+}
+
+impl<T> Add<Matrix<T>> for &Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn add(self, mut other: Matrix<T>) -> Self::Output {
+        assert!(self.get_cols() == other.get_cols() && self.get_rows() == other.get_rows(), "矩阵大小不匹配");
+        for (element, value) in other.data.iter_mut().zip(self.data.iter()) {
+            *element = *value + *element;
+        }
+        return other;
+    }
+}
+
+impl<T> Sub<&Matrix<T>> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn sub(mut self, other: &Matrix<T>) -> Self::Output {
+        self -= other;
+        return self;
+    }
+}
+
+impl<T> Sub for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn sub(self, other: Matrix<T>) -> Self::Output {
+        return self - &other;
+    }
+}
+
+impl<T> Sub<Matrix<T>> for &Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn sub(self, mut other: Matrix<T>) -> Self::Output {
+        assert!(self.get_cols() == other.get_cols() && self.get_rows() == other.get_rows(), "矩阵大小不匹配");
+        for (element, value) in other.data.iter_mut().zip(self.data.iter()) {
+            *element = *value - *element;
+        }
+        return other;
+    }
+}
+
+// 矩阵乘法的结果大小一般与两个操作数都不同，统一转给 &Matrix * &Matrix
+impl<T> Mul<&Matrix<T>> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: &Matrix<T>) -> Self::Output {
+        return &self * other;
+    }
+}
+
+impl<T> Mul for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Matrix<T>) -> Self::Output {
+        return &self * &other;
+    }
+}
+
+impl<T> Mul<Matrix<T>> for &Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Matrix<T>) -> Self::Output {
+        return self * &other;
+    }
+}
+
+impl<T> Mul<T> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn mul(mut self, rhs: T) -> Self::Output {
+        self *= rhs;
+        return self;
+    }
+}
+
+impl<T> Div<T> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn div(mut self, rhs: T) -> Self::Output {
+        self /= rhs;
+        return self;
+    }
+}
+
+impl<T> Div<T> for &Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display {
+    type Output = Matrix<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        return self.clone() / rhs;
+    }
+}
+
+impl<T> Neg for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + Neg<Output = T> {
+    type Output = Matrix<T>;
+
+    fn neg(mut self) -> Self::Output {
+        for element in self.data.iter_mut() {
+            *element = -*element;
+        }
+        return self;
+    }
+}
+
+impl<T> Neg for &Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + Neg<Output = T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Self::Output {
+        return -self.clone();
+    }
+}
+
+// 孤儿规则不允许为泛型 T 实现 T * Matrix<T>，只能逐个为具体的数值类型实现
+macro_rules! impl_scalar_left_mul {
+    ($($scalar:ty),*) => {
+        $(
+            impl Mul<Matrix<$scalar>> for $scalar {
+                type Output = Matrix<$scalar>;
+
+                fn mul(self, rhs: Matrix<$scalar>) -> Self::Output {
+                    return rhs * self;
+                }
+            }
+
+            impl Mul<&Matrix<$scalar>> for $scalar {
+                type Output = Matrix<$scalar>;
+
+                fn mul(self, rhs: &Matrix<$scalar>) -> Self::Output {
+                    return rhs * self;
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_left_mul!(f32, f64, i32, i64);
+
+#[cfg(feature = "rand")]
 pub fn random_i64_matrix(rows:usize, cols: usize, random_seed: i64, range_lower_bound: i64, range_upper_bound: i64) -> Matrix<i64>{
     let mut data:Vec<i64> = Vec::new();
     let seed_u64: u64 = random_seed as u64;
@@ -304,6 +765,7 @@ pub fn random_i64_matrix(rows:usize, cols: usize, random_seed: i64, range_lower_
     return result;
 }
 
+#[cfg(feature = "rand")]
 pub fn random_f64_matrix(rows:usize, cols: usize, random_seed: i64, range_lower_bound: f64, range_upper_bound: f64) -> Matrix<f64>{
     let mut data:Vec<f64> = Vec::new();
     let seed_u64: u64 = random_seed as u64;
@@ -0,0 +1,162 @@
+use std::fmt;
+use std::io::Write;
+use std::ops::{Add, Div, Mul, Sub};
+use num_traits::One;
+use crate::matrix::Matrix;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OutputStyle {
+    Plain,
+    Latex,
+    Markdown,
+    Html,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+pub struct MatrixFormatter {
+    pub style: OutputStyle,
+    // None 表示直接使用元素自身的 Display 输出
+    pub precision: Option<usize>,
+    // None 表示按每列最长元素自动确定列宽
+    pub width: Option<usize>,
+    pub alignment: Alignment,
+}
+
+impl Default for MatrixFormatter {
+    fn default() -> Self {
+        MatrixFormatter {
+            style: OutputStyle::Plain,
+            precision: None,
+            width: None,
+            alignment: Alignment::Right,
+        }
+    }
+}
+
+impl MatrixFormatter {
+    pub fn write<T, W>(&self, matrix: &Matrix<T>, writer: &mut W) -> std::io::Result<()> where
+            T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display,
+            W: Write {
+        let cells = self.format_cells(matrix);
+        let widths = self.column_widths(&cells, matrix.get_cols());
+        match self.style {
+            OutputStyle::Plain => {
+                let inner_width: usize = widths.iter().map(|width| width + 1).sum::<usize>() + 1;
+                writeln!(writer, "┌{}┐", " ".repeat(inner_width))?;
+                for row in &cells {
+                    write!(writer, "│")?;
+                    for (col, cell) in row.iter().enumerate() {
+                        write!(writer, " {}", self.align(cell, widths[col]))?;
+                    }
+                    writeln!(writer, " │")?;
+                }
+                writeln!(writer, "└{}┘", " ".repeat(inner_width))?;
+            }
+            OutputStyle::Latex => {
+                writeln!(writer, "\\begin{{bmatrix}}")?;
+                for (row_index, row) in cells.iter().enumerate() {
+                    let aligned: Vec<String> = row.iter().enumerate().map(|(col, cell)| self.align(cell, widths[col])).collect();
+                    let ending = if row_index + 1 < cells.len() { " \\\\" } else { "" };
+                    writeln!(writer, "{}{}", aligned.join(" & "), ending)?;
+                }
+                writeln!(writer, "\\end{{bmatrix}}")?;
+            }
+            OutputStyle::Markdown => {
+                let header: Vec<String> = (0..matrix.get_cols()).map(|col| self.align(&format!("c{}", col), widths[col])).collect();
+                writeln!(writer, "| {} |", header.join(" | "))?;
+                let rule: Vec<String> = widths.iter().map(|width| {
+                    let dashes = "-".repeat((*width).max(3) - 1);
+                    match self.alignment {
+                        Alignment::Left => format!(":{}", dashes),
+                        Alignment::Center => format!(":{}:", &dashes[1..]),
+                        Alignment::Right => format!("{}:", dashes),
+                    }
+                }).collect();
+                writeln!(writer, "|{}|", rule.iter().map(|part| format!(" {} ", part)).collect::<Vec<String>>().join("|"))?;
+                for row in &cells {
+                    let aligned: Vec<String> = row.iter().enumerate().map(|(col, cell)| self.align(cell, widths[col])).collect();
+                    writeln!(writer, "| {} |", aligned.join(" | "))?;
+                }
+            }
+            OutputStyle::Html => {
+                let align = match self.alignment {
+                    Alignment::Left => "left",
+                    Alignment::Center => "center",
+                    Alignment::Right => "right",
+                };
+                writeln!(writer, "<table>")?;
+                for row in &cells {
+                    write!(writer, "  <tr>")?;
+                    for cell in row {
+                        write!(writer, "<td style=\"text-align: {}\">{}</td>", align, cell)?;
+                    }
+                    writeln!(writer, "</tr>")?;
+                }
+                writeln!(writer, "</table>")?;
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn format<T>(&self, matrix: &Matrix<T>) -> String where
+            T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write(matrix, &mut buffer).expect("写入内存缓冲区不会失败");
+        return String::from_utf8(buffer).expect("输出均为 UTF-8");
+    }
+
+    fn format_cells<T>(&self, matrix: &Matrix<T>) -> Vec<Vec<String>> where
+            T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+        let mut cells: Vec<Vec<String>> = Vec::with_capacity(matrix.get_rows());
+        for row in 0..matrix.get_rows() {
+            let mut cell_row: Vec<String> = Vec::with_capacity(matrix.get_cols());
+            for col in 0..matrix.get_cols() {
+                let cell = match self.precision {
+                    Some(precision) => format!("{:.*}", precision, matrix.get(row, col)),
+                    None => format!("{}", matrix.get(row, col)),
+                };
+                cell_row.push(cell);
+            }
+            cells.push(cell_row);
+        }
+        return cells;
+    }
+
+    fn column_widths(&self, cells: &[Vec<String>], cols: usize) -> Vec<usize> {
+        let mut widths: Vec<usize> = vec![self.width.unwrap_or(0); cols];
+        if self.width.is_none() {
+            for row in cells {
+                for (col, cell) in row.iter().enumerate() {
+                    widths[col] = widths[col].max(cell.chars().count());
+                }
+            }
+        }
+        return widths;
+    }
+
+    fn align(&self, cell: &str, width: usize) -> String {
+        return match self.alignment {
+            Alignment::Left => format!("{:<width$}", cell, width = width),
+            Alignment::Center => format!("{:^width$}", cell, width = width),
+            Alignment::Right => format!("{:>width$}", cell, width = width),
+        }
+    }
+}
+
+// 使用自动列宽的方框格式，支持 {:.3} 这样的精度参数
+impl<T> fmt::Display for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let formatter = MatrixFormatter {
+            precision: f.precision(),
+            ..MatrixFormatter::default()
+        };
+        return write!(f, "{}", formatter.format(self));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::matrix::Matrix;
+
+// Higham (2005) 13 阶 Padé 近似的系数
+const PADE_13: [f64; 14] = [
+    64764752532480000.0,
+    32382376266240000.0,
+    7771770303897600.0,
+    1187353796428800.0,
+    129060195264000.0,
+    10559470521600.0,
+    670442572800.0,
+    33522128640.0,
+    1323241920.0,
+    40840800.0,
+    960960.0,
+    16380.0,
+    182.0,
+    1.0,
+];
+// 13 阶 Padé 近似在 1-范数下的适用上界
+const THETA_13: f64 = 5.371920351148152;
+const MAX_ITERATIONS: usize = 100;
+
+impl Matrix<f64> {
+    pub fn norm_1(target:&Matrix<f64>) -> f64{
+        let mut result: f64 = 0.0;
+        for col in 0..target.get_cols() {
+            let mut sum = 0.0;
+            for row in 0..target.get_rows() {
+                sum += target.get(row, col).abs();
+            }
+            result = result.max(sum);
+        }
+        return result;
+    }
+
+    // Padé 分母矩阵奇异时返回 None
+    pub fn expm(target:&Matrix<f64>) -> Option<Matrix<f64>>{
+        assert_eq!(target.get_rows(), target.get_cols(), "只有方阵可以求指数");
+        let norm = Matrix::norm_1(target);
+        let mut squarings: u32 = 0;
+        if norm > THETA_13 {
+            squarings = (norm / THETA_13).log2().ceil() as u32;
+        }
+        let scaled = target * (1.0 / 2f64.powi(squarings as i32));
+
+        let size = target.get_rows();
+        let identity: Matrix<f64> = Matrix::identity(size);
+        let a2 = &scaled * &scaled;
+        let a4 = &a2 * &a2;
+        let a6 = &a2 * &a4;
+        let b = &PADE_13;
+
+        let mut u = &(&(&a6 * b[13]) + &(&a4 * b[11])) + &(&a2 * b[9]);
+        u = &a6 * &u;
+        u = &(&(&(&u + &(&a6 * b[7])) + &(&a4 * b[5])) + &(&a2 * b[3])) + &(&identity * b[1]);
+        u = &scaled * &u;
+        let mut v = &(&(&a6 * b[12]) + &(&a4 * b[10])) + &(&a2 * b[8]);
+        v = &a6 * &v;
+        v = &(&(&(&v + &(&a6 * b[6])) + &(&a4 * b[4])) + &(&a2 * b[2])) + &(&identity * b[0]);
+
+        let numerator = &v + &u;
+        let denominator = &v - &u;
+        let mut result_matrix = Matrix::solve(&denominator, &numerator)?;
+        for _ in 0..squarings {
+            result_matrix = &result_matrix * &result_matrix;
+        }
+        return Some(result_matrix);
+    }
+
+    // Denman–Beavers 迭代，要求矩阵没有非正实特征值。迭代中出现奇异矩阵或者不收敛时返回 None
+    pub fn sqrtm(target:&Matrix<f64>) -> Option<Matrix<f64>>{
+        assert_eq!(target.get_rows(), target.get_cols(), "只有方阵可以求平方根");
+        let size = target.get_rows();
+        let mut y: Matrix<f64> = target.clone();
+        let mut z: Matrix<f64> = Matrix::identity(size);
+        for _ in 0..MAX_ITERATIONS {
+            let y_inverse = Matrix::inverse_f64(&y)?;
+            let z_inverse = Matrix::inverse_f64(&z)?;
+            let next_y = &(&y + &z_inverse) * 0.5;
+            let next_z = &(&z + &y_inverse) * 0.5;
+            let step = Matrix::norm_1(&(&next_y - &y));
+            y = next_y;
+            z = next_z;
+            if !step.is_finite() {
+                return None;
+            }
+            if step <= 1e-14 * Matrix::norm_1(&y) {
+                return Some(y);
+            }
+        }
+        return None;
+    }
+
+    // 逆缩放平方法：反复开方直到接近单位阵，再用级数求 log(I + X)。开方失败或者不收敛时返回 None
+    pub fn logm(target:&Matrix<f64>) -> Option<Matrix<f64>>{
+        assert_eq!(target.get_rows(), target.get_cols(), "只有方阵可以求对数");
+        let size = target.get_rows();
+        let identity: Matrix<f64> = Matrix::identity(size);
+        let mut root: Matrix<f64> = target.clone();
+        let mut square_roots: i32 = 0;
+        while Matrix::norm_1(&(&root - &identity)) > 0.25 {
+            if square_roots >= 64 {
+                return None;
+            }
+            root = Matrix::sqrtm(&root)?;
+            square_roots += 1;
+        }
+        let x = &root - &identity;
+        let mut power = x.clone();
+        let mut result_matrix: Matrix<f64> = Matrix::new(size, size);
+        for term in 1..=60 {
+            let sign = if term % 2 == 1 { 1.0 } else { -1.0 };
+            let coefficient = sign / term as f64;
+            result_matrix = &result_matrix + &(&power * coefficient);
+            power = &power * &x;
+        }
+        return Some(&result_matrix * 2f64.powi(square_roots));
+    }
+
+    // 负指数时矩阵奇异返回 None
+    pub fn powi(target:&Matrix<f64>, exponent:i32) -> Option<Matrix<f64>>{
+        let result_matrix = Matrix::pow(target, exponent.unsigned_abs());
+        return if exponent < 0 {
+            Matrix::inverse_f64(&result_matrix)
+        } else {
+            Some(result_matrix)
+        }
+    }
+
+    // 非整数指数通过 expm(p logm(A)) 计算，矩阵奇异或者有非正实特征值时返回 None
+    pub fn powf(target:&Matrix<f64>, exponent:f64) -> Option<Matrix<f64>>{
+        if exponent.fract() == 0.0 && exponent.abs() <= i32::MAX as f64 {
+            return Matrix::powi(target, exponent as i32);
+        }
+        return Matrix::expm(&(&Matrix::logm(target)? * exponent));
+    }
+
+    fn inverse_f64(target:&Matrix<f64>) -> Option<Matrix<f64>>{
+        let identity: Matrix<f64> = Matrix::identity(target.get_rows());
+        return Matrix::solve(target, &identity);
+    }
+}
@@ -0,0 +1,574 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+use num_traits::One;
+use crate::matrix::Matrix;
+use crate::matrix_parser::ParseMatrixError;
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+// 二进制格式：魔数(4) + 版本 u16 + 元素类型 u8 + 保留 u8 + 行数 u64 + 列数 u64，均为小端
+const BINARY_MAGIC: &[u8] = b"MATB";
+const BINARY_VERSION: u16 = 1;
+// 按文件头预先分配的元素个数上限，文件头不可信，更多的元素边读边扩容
+const MAX_RESERVED_ELEMENTS: usize = 1 << 16;
+
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub has_header: bool,
+    // 写出表头时使用的列名，为空则使用 c0, c1, ...
+    pub column_names: Vec<String>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            has_header: false,
+            column_names: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MarketFormat {
+    Array,
+    Coordinate,
+}
+
+// Matrix Market 文件头中的 field 字段
+pub trait MarketField {
+    const FIELD: &'static str;
+}
+
+impl MarketField for f64 {
+    const FIELD: &'static str = "real";
+}
+
+impl MarketField for i64 {
+    const FIELD: &'static str = "integer";
+}
+
+// 可以存入 .npy 的元素类型，均为 8 字节小端
+pub trait NpyElement: Sized {
+    const DESCR: &'static str;
+    fn to_le(self) -> [u8; 8];
+    fn from_le(bytes: [u8; 8]) -> Self;
+}
+
+impl NpyElement for f64 {
+    const DESCR: &'static str = "<f8";
+    fn to_le(self) -> [u8; 8] {
+        self.to_le_bytes()
+    }
+    fn from_le(bytes: [u8; 8]) -> Self {
+        f64::from_le_bytes(bytes)
+    }
+}
+
+impl NpyElement for i64 {
+    const DESCR: &'static str = "<i8";
+    fn to_le(self) -> [u8; 8] {
+        self.to_le_bytes()
+    }
+    fn from_le(bytes: [u8; 8]) -> Self {
+        i64::from_le_bytes(bytes)
+    }
+}
+
+// 二进制格式中记录的元素类型编号
+pub trait BinaryElement: NpyElement {
+    const TYPE_CODE: u8;
+}
+
+impl BinaryElement for f64 {
+    const TYPE_CODE: u8 = 1;
+}
+
+impl BinaryElement for i64 {
+    const TYPE_CODE: u8 = 2;
+}
+
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + FromStr {
+    pub fn read_csv<R: BufRead>(reader: R, options: &CsvOptions) -> Result<Matrix<T>, ParseMatrixError> {
+        let mut rows: usize = 0;
+        let mut cols: usize = 0;
+        let mut data: Vec<T> = Vec::new();
+        let mut skip_header = options.has_header;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if skip_header {
+                skip_header = false;
+                continue;
+            }
+            let tokens: Vec<&str> = line.split(options.delimiter).map(|token| token.trim()).collect();
+            if rows == 0 {
+                cols = tokens.len();
+            } else if tokens.len() != cols {
+                return Err(ParseMatrixError::RaggedRow { row: rows, expected: cols, found: tokens.len() });
+            }
+            for (col, token) in tokens.iter().enumerate() {
+                data.push(parse_token(token, rows, col)?);
+            }
+            rows += 1;
+        }
+        if rows == 0 {
+            return Err(ParseMatrixError::Empty);
+        }
+        return Ok(Matrix::init(rows, cols, data));
+    }
+
+    pub fn write_csv<W: Write>(matrix: &Matrix<T>, writer: &mut W, options: &CsvOptions) -> std::io::Result<()> {
+        let delimiter = options.delimiter.to_string();
+        if options.has_header {
+            for col in 0..matrix.get_cols() {
+                if col > 0 {
+                    write!(writer, "{}", delimiter)?;
+                }
+                match options.column_names.get(col) {
+                    Some(name) => write!(writer, "{}", name)?,
+                    None => write!(writer, "c{}", col)?,
+                }
+            }
+            writeln!(writer)?;
+        }
+        for row in 0..matrix.get_rows() {
+            for col in 0..matrix.get_cols() {
+                if col > 0 {
+                    write!(writer, "{}", delimiter)?;
+                }
+                write!(writer, "{}", matrix.get(row, col))?;
+            }
+            writeln!(writer)?;
+        }
+        return Ok(());
+    }
+
+    pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Matrix<T>, ParseMatrixError> {
+        let (header, mut lines) = read_market_header(reader)?;
+        if header.field == "complex" {
+            return Err(ParseMatrixError::InvalidHeader("complex 矩阵请使用 read_matrix_market_complex".to_string()));
+        }
+        let (rows, cols, entries) = read_market_size(&mut lines, &header)?;
+        let mut result_matrix: Matrix<T> = Matrix::new(rows, cols);
+        let values_per_entry = if header.field == "pattern" { 0 } else { 1 };
+        for index in 0..entries {
+            let (row, col, tokens) = read_market_entry(&mut lines, &header, rows, cols, index, values_per_entry)?;
+            let value: T = if values_per_entry == 0 { T::one() } else { parse_token(&tokens[0], row, col)? };
+            result_matrix.set(row, col, value);
+            if row != col {
+                match header.symmetry.as_str() {
+                    "symmetric" | "hermitian" => result_matrix.set(col, row, value),
+                    "skew-symmetric" => result_matrix.set(col, row, T::default() - value),
+                    _ => {}
+                }
+            }
+        }
+        return Ok(result_matrix);
+    }
+}
+
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + MarketField {
+    pub fn write_matrix_market<W: Write>(matrix: &Matrix<T>, writer: &mut W, format: MarketFormat) -> std::io::Result<()> {
+        match format {
+            MarketFormat::Array => {
+                writeln!(writer, "%%MatrixMarket matrix array {} general", T::FIELD)?;
+                writeln!(writer, "{} {}", matrix.get_rows(), matrix.get_cols())?;
+                for col in 0..matrix.get_cols() {
+                    for row in 0..matrix.get_rows() {
+                        writeln!(writer, "{}", matrix.get(row, col))?;
+                    }
+                }
+            }
+            MarketFormat::Coordinate => {
+                let mut non_zeros: usize = 0;
+                for row in 0..matrix.get_rows() {
+                    for col in 0..matrix.get_cols() {
+                        if *matrix.get(row, col) != T::default() {
+                            non_zeros += 1;
+                        }
+                    }
+                }
+                writeln!(writer, "%%MatrixMarket matrix coordinate {} general", T::FIELD)?;
+                writeln!(writer, "{} {} {}", matrix.get_rows(), matrix.get_cols(), non_zeros)?;
+                for row in 0..matrix.get_rows() {
+                    for col in 0..matrix.get_cols() {
+                        let value = *matrix.get(row, col);
+                        if value != T::default() {
+                            writeln!(writer, "{} {} {}", row + 1, col + 1, value)?;
+                        }
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+}
+
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + NpyElement {
+    pub fn read_npy<R: Read>(reader: &mut R) -> Result<Matrix<T>, ParseMatrixError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic[..6] != NPY_MAGIC {
+            return Err(ParseMatrixError::InvalidHeader("不是 .npy 文件".to_string()));
+        }
+        let header_length = match magic[6] {
+            1 => {
+                let mut length = [0u8; 2];
+                reader.read_exact(&mut length)?;
+                u16::from_le_bytes(length) as usize
+            }
+            2 | 3 => {
+                let mut length = [0u8; 4];
+                reader.read_exact(&mut length)?;
+                u32::from_le_bytes(length) as usize
+            }
+            version => return Err(ParseMatrixError::InvalidHeader(format!("不支持的 .npy 版本 {}", version))),
+        };
+        let mut header_bytes = vec![0u8; header_length];
+        reader.read_exact(&mut header_bytes)?;
+        let header = String::from_utf8_lossy(&header_bytes).to_string();
+
+        let descr = npy_header_value(&header, "descr")?;
+        if descr.trim_matches(|c| c == '\'' || c == '"') != T::DESCR {
+            return Err(ParseMatrixError::InvalidHeader(format!("元素类型 {} 与 {} 不符", descr, T::DESCR)));
+        }
+        let fortran_order = npy_header_value(&header, "fortran_order")? == "True";
+        let shape_text = npy_header_value(&header, "shape")?;
+        let shape: Vec<usize> = shape_text.trim_matches(|c| c == '(' || c == ')')
+            .split(',')
+            .map(|token| token.trim())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()
+            .map_err(|_| ParseMatrixError::InvalidHeader(format!("无法解析形状 {}", shape_text)))?;
+        let (rows, cols) = match shape.as_slice() {
+            [length] => (1, *length),
+            [rows, cols] => (*rows, *cols),
+            _ => return Err(ParseMatrixError::InvalidHeader(format!("只支持一维或二维数组，实际为 {}", shape_text))),
+        };
+
+        let count = rows.checked_mul(cols)
+            .ok_or_else(|| ParseMatrixError::InvalidHeader(format!("形状 {} 超出范围", shape_text)))?;
+        // 先把元素读完再建矩阵，文件头声称的形状比实际内容大时在读取处报错
+        let mut data: Vec<T> = Vec::with_capacity(count.min(MAX_RESERVED_ELEMENTS));
+        let mut element = [0u8; 8];
+        for _ in 0..count {
+            reader.read_exact(&mut element)?;
+            data.push(T::from_le(element));
+        }
+        if !fortran_order {
+            return Ok(Matrix::init(rows, cols, data));
+        }
+        let mut result_matrix: Matrix<T> = Matrix::new(rows, cols);
+        for (index, value) in data.into_iter().enumerate() {
+            result_matrix.set(index % rows, index / rows, value);
+        }
+        return Ok(result_matrix);
+    }
+
+    // 头部超过 u16 能表示的长度时自动使用 2.0 版本
+    pub fn write_npy<W: Write>(matrix: &Matrix<T>, writer: &mut W) -> std::io::Result<()> {
+        let dictionary = format!("{{'descr': '{}', 'fortran_order': False, 'shape': ({}, {}), }}",
+            T::DESCR, matrix.get_rows(), matrix.get_cols());
+        let mut version: u8 = 1;
+        let mut prefix_length = NPY_MAGIC.len() + 2 + 2;
+        let mut padded_length = (prefix_length + dictionary.len() + 1).div_ceil(64) * 64;
+        if padded_length - prefix_length > u16::MAX as usize {
+            version = 2;
+            prefix_length = NPY_MAGIC.len() + 2 + 4;
+            padded_length = (prefix_length + dictionary.len() + 1).div_ceil(64) * 64;
+        }
+        let header_length = padded_length - prefix_length;
+        writer.write_all(NPY_MAGIC)?;
+        writer.write_all(&[version, 0])?;
+        if version == 1 {
+            writer.write_all(&(header_length as u16).to_le_bytes())?;
+        } else {
+            writer.write_all(&(header_length as u32).to_le_bytes())?;
+        }
+        writer.write_all(dictionary.as_bytes())?;
+        writer.write_all(" ".repeat(header_length - dictionary.len() - 1).as_bytes())?;
+        writer.write_all(b"\n")?;
+        for row in 0..matrix.get_rows() {
+            for col in 0..matrix.get_cols() {
+                writer.write_all(&matrix.get(row, col).to_le())?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + BinaryElement {
+    pub fn write_binary<W: Write>(matrix: &Matrix<T>, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(BINARY_MAGIC)?;
+        writer.write_all(&BINARY_VERSION.to_le_bytes())?;
+        writer.write_all(&[T::TYPE_CODE, 0])?;
+        writer.write_all(&(matrix.get_rows() as u64).to_le_bytes())?;
+        writer.write_all(&(matrix.get_cols() as u64).to_le_bytes())?;
+        for row in 0..matrix.get_rows() {
+            for col in 0..matrix.get_cols() {
+                writer.write_all(&matrix.get(row, col).to_le())?;
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn read_binary<R: Read>(reader: &mut R) -> Result<Matrix<T>, ParseMatrixError> {
+        let mut header = [0u8; 24];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != BINARY_MAGIC {
+            return Err(ParseMatrixError::InvalidHeader("不是矩阵二进制文件".to_string()));
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != BINARY_VERSION {
+            return Err(ParseMatrixError::InvalidHeader(format!("不支持的二进制版本 {}", version)));
+        }
+        if header[6] != T::TYPE_CODE {
+            return Err(ParseMatrixError::InvalidHeader(format!("元素类型编号 {} 与 {} 不符", header[6], T::TYPE_CODE)));
+        }
+        let mut size = [0u8; 8];
+        size.copy_from_slice(&header[8..16]);
+        let rows = u64::from_le_bytes(size) as usize;
+        size.copy_from_slice(&header[16..24]);
+        let cols = u64::from_le_bytes(size) as usize;
+        let count = rows.checked_mul(cols)
+            .ok_or_else(|| ParseMatrixError::InvalidHeader(format!("矩阵大小 {}x{} 超出范围", rows, cols)))?;
+        let mut data: Vec<T> = Vec::with_capacity(count.min(MAX_RESERVED_ELEMENTS));
+        let mut element = [0u8; 8];
+        for _ in 0..count {
+            reader.read_exact(&mut element)?;
+            data.push(T::from_le(element));
+        }
+        return Ok(Matrix::init(rows, cols, data));
+    }
+
+    pub fn save_binary(matrix: &Matrix<T>, file_name: &String) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(file_name)?);
+        Matrix::write_binary(matrix, &mut writer)?;
+        return writer.flush();
+    }
+
+    pub fn load_binary(file_name: &String) -> Result<Matrix<T>, ParseMatrixError> {
+        let mut reader = BufReader::new(File::open(file_name)?);
+        return Matrix::read_binary(&mut reader);
+    }
+}
+
+impl Matrix<f64> {
+    // 复数矩阵按实部、虚部两个矩阵返回
+    pub fn read_matrix_market_complex<R: BufRead>(reader: R) -> Result<(Matrix<f64>, Matrix<f64>), ParseMatrixError> {
+        let (header, mut lines) = read_market_header(reader)?;
+        if header.field != "complex" {
+            return Err(ParseMatrixError::InvalidHeader(format!("field 为 {}，不是 complex", header.field)));
+        }
+        let (rows, cols, entries) = read_market_size(&mut lines, &header)?;
+        let mut real: Matrix<f64> = Matrix::new(rows, cols);
+        let mut imaginary: Matrix<f64> = Matrix::new(rows, cols);
+        for index in 0..entries {
+            let (row, col, tokens) = read_market_entry(&mut lines, &header, rows, cols, index, 2)?;
+            let real_value: f64 = parse_token(&tokens[0], row, col)?;
+            let imaginary_value: f64 = parse_token(&tokens[1], row, col)?;
+            real.set(row, col, real_value);
+            imaginary.set(row, col, imaginary_value);
+            if row != col {
+                let (mirror_real, mirror_imaginary) = match header.symmetry.as_str() {
+                    "symmetric" => (real_value, imaginary_value),
+                    "skew-symmetric" => (-real_value, -imaginary_value),
+                    "hermitian" => (real_value, -imaginary_value),
+                    _ => continue,
+                };
+                real.set(col, row, mirror_real);
+                imaginary.set(col, row, mirror_imaginary);
+            }
+        }
+        return Ok((real, imaginary));
+    }
+
+    pub fn write_matrix_market_complex<W: Write>(real: &Matrix<f64>, imaginary: &Matrix<f64>, writer: &mut W, format: MarketFormat) -> std::io::Result<()> {
+        assert!(real.get_rows() == imaginary.get_rows() && real.get_cols() == imaginary.get_cols(), "矩阵大小不匹配");
+        match format {
+            MarketFormat::Array => {
+                writeln!(writer, "%%MatrixMarket matrix array complex general")?;
+                writeln!(writer, "{} {}", real.get_rows(), real.get_cols())?;
+                for col in 0..real.get_cols() {
+                    for row in 0..real.get_rows() {
+                        writeln!(writer, "{} {}", real.get(row, col), imaginary.get(row, col))?;
+                    }
+                }
+            }
+            MarketFormat::Coordinate => {
+                let is_non_zero = |row: usize, col: usize| *real.get(row, col) != 0.0 || *imaginary.get(row, col) != 0.0;
+                let mut non_zeros: usize = 0;
+                for row in 0..real.get_rows() {
+                    for col in 0..real.get_cols() {
+                        if is_non_zero(row, col) {
+                            non_zeros += 1;
+                        }
+                    }
+                }
+                writeln!(writer, "%%MatrixMarket matrix coordinate complex general")?;
+                writeln!(writer, "{} {} {}", real.get_rows(), real.get_cols(), non_zeros)?;
+                for row in 0..real.get_rows() {
+                    for col in 0..real.get_cols() {
+                        if is_non_zero(row, col) {
+                            writeln!(writer, "{} {} {} {}", row + 1, col + 1, real.get(row, col), imaginary.get(row, col))?;
+                        }
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+}
+
+struct MarketHeader {
+    format: MarketFormat,
+    field: String,
+    symmetry: String,
+}
+
+type MarketLines<R> = std::iter::Filter<std::io::Lines<R>, fn(&std::io::Result<String>) -> bool>;
+
+fn is_market_content(line: &std::io::Result<String>) -> bool {
+    return match line {
+        Ok(text) => !text.trim().is_empty() && !text.starts_with('%'),
+        Err(_) => true,
+    }
+}
+
+fn read_market_header<R: BufRead>(reader: R) -> Result<(MarketHeader, MarketLines<R>), ParseMatrixError> {
+    let mut lines = reader.lines();
+    let banner = lines.next().ok_or(ParseMatrixError::Empty)??;
+    let words: Vec<String> = banner.split_whitespace().map(|word| word.to_lowercase()).collect();
+    if words.len() != 5 || words[0] != "%%matrixmarket" || words[1] != "matrix" {
+        return Err(ParseMatrixError::InvalidHeader(banner));
+    }
+    let format = match words[2].as_str() {
+        "array" => MarketFormat::Array,
+        "coordinate" => MarketFormat::Coordinate,
+        _ => return Err(ParseMatrixError::InvalidHeader(banner)),
+    };
+    if !["real", "integer", "complex", "pattern"].contains(&words[3].as_str())
+        || !["general", "symmetric", "skew-symmetric", "hermitian"].contains(&words[4].as_str())
+        || (format == MarketFormat::Array && words[3] == "pattern") {
+        return Err(ParseMatrixError::InvalidHeader(banner));
+    }
+    let header = MarketHeader {
+        format,
+        field: words[3].clone(),
+        symmetry: words[4].clone(),
+    };
+    let filter: fn(&std::io::Result<String>) -> bool = is_market_content;
+    return Ok((header, lines.filter(filter)));
+}
+
+fn read_market_size<R: BufRead>(lines: &mut MarketLines<R>, header: &MarketHeader) -> Result<(usize, usize, usize), ParseMatrixError> {
+    let line = lines.next().ok_or(ParseMatrixError::Empty)??;
+    let sizes: Vec<usize> = line.split_whitespace()
+        .map(|token| token.parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| ParseMatrixError::InvalidHeader(line.clone()))?;
+    let (rows, cols) = match sizes.as_slice() {
+        [rows, cols, ..] => (*rows, *cols),
+        _ => return Err(ParseMatrixError::InvalidHeader(line)),
+    };
+    // 对称存储按下三角镜像到上三角，只对方阵有意义
+    if header.symmetry != "general" && rows != cols {
+        return Err(ParseMatrixError::InvalidHeader(format!("{} 矩阵必须是方阵，实际为 {}x{}", header.symmetry, rows, cols)));
+    }
+    // 行列数来自文件，乘积溢出时按文件头无效处理
+    let size = match rows.checked_mul(cols) {
+        Some(size) => size,
+        None => return Err(ParseMatrixError::InvalidHeader(line)),
+    };
+    return match (header.format, sizes.as_slice()) {
+        (MarketFormat::Array, [_, _]) => {
+            if header.symmetry == "general" {
+                return Ok((rows, cols, size));
+            }
+            // 对称存储只保存下三角（反对称不含对角线），0 行时没有元素
+            let diagonal = if header.symmetry == "skew-symmetric" { 0 } else { rows };
+            match rows.checked_mul(rows.saturating_sub(1)) {
+                Some(product) => Ok((rows, cols, diagonal + product / 2)),
+                None => Err(ParseMatrixError::InvalidHeader(line)),
+            }
+        }
+        (MarketFormat::Coordinate, [_, _, entries]) => Ok((rows, cols, *entries)),
+        _ => Err(ParseMatrixError::InvalidHeader(line)),
+    }
+}
+
+// 返回第 index 个元素的 (行, 列, 数值字段)，行列均已转换为从 0 开始
+fn read_market_entry<R: BufRead>(lines: &mut MarketLines<R>, header: &MarketHeader, rows: usize, cols: usize,
+                                 index: usize, values_per_entry: usize) -> Result<(usize, usize, Vec<String>), ParseMatrixError> {
+    let line = lines.next().ok_or(ParseMatrixError::Empty)??;
+    let tokens: Vec<String> = line.split_whitespace().map(|token| token.to_string()).collect();
+    let (row, col, values) = match header.format {
+        MarketFormat::Array => {
+            let (row, col) = if header.symmetry == "general" {
+                (index % rows, index / rows)
+            } else {
+                market_lower_position(index, rows, header.symmetry == "skew-symmetric")
+            };
+            (row, col, tokens)
+        }
+        MarketFormat::Coordinate => {
+            if tokens.len() < 2 {
+                return Err(ParseMatrixError::RaggedRow { row: index, expected: 2 + values_per_entry, found: tokens.len() });
+            }
+            let row: usize = parse_token(&tokens[0], index, 0)?;
+            let col: usize = parse_token(&tokens[1], index, 1)?;
+            if row == 0 || col == 0 || row > rows || col > cols {
+                return Err(ParseMatrixError::InvalidElement { row: index, col: 0, token: line.clone() });
+            }
+            (row - 1, col - 1, tokens[2..].to_vec())
+        }
+    };
+    if values.len() != values_per_entry {
+        return Err(ParseMatrixError::RaggedRow { row: index, expected: values_per_entry, found: values.len() });
+    }
+    return Ok((row, col, values));
+}
+
+// 按列优先遍历下三角时第 index 个元素的位置
+fn market_lower_position(index: usize, size: usize, strict: bool) -> (usize, usize) {
+    let mut remaining = index;
+    for col in 0..size {
+        let start = if strict { col + 1 } else { col };
+        let length = size - start;
+        if remaining < length {
+            return (start + remaining, col);
+        }
+        remaining -= length;
+    }
+    return (size, size);
+}
+
+fn parse_token<T: FromStr>(token: &str, row: usize, col: usize) -> Result<T, ParseMatrixError> {
+    return token.parse::<T>().map_err(|_| ParseMatrixError::InvalidElement {
+        row,
+        col,
+        token: token.to_string(),
+    });
+}
+
+fn npy_header_value(header: &str, key: &str) -> Result<String, ParseMatrixError> {
+    let missing = || ParseMatrixError::InvalidHeader(format!("缺少 {}", key));
+    let quoted_key = format!("'{}'", key);
+    let start = header.find(&quoted_key).ok_or_else(missing)? + quoted_key.len();
+    let rest = header[start..].trim_start().strip_prefix(':').ok_or_else(missing)?.trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')').ok_or_else(missing)? + 1
+    } else {
+        rest.find([',', '}']).ok_or_else(missing)?
+    };
+    return Ok(rest[..end].trim().to_string());
+}
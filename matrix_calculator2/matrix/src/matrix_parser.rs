@@ -0,0 +1,180 @@
+use std::fmt;
+use std::fs;
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+use num_traits::One;
+use crate::matrix::Matrix;
+
+#[derive(Debug)]
+pub enum ParseMatrixError {
+    Empty,
+    UnbalancedBrackets,
+    RaggedRow { row: usize, expected: usize, found: usize },
+    InvalidElement { row: usize, col: usize, token: String },
+    InvalidHeader(String),
+    MultipleMatrices(usize),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ParseMatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMatrixError::Empty => write!(f, "矩阵为空"),
+            ParseMatrixError::UnbalancedBrackets => write!(f, "括号不匹配"),
+            ParseMatrixError::RaggedRow { row, expected, found } =>
+                write!(f, "第 {} 行有 {} 个元素，应为 {} 个", row + 1, found, expected),
+            ParseMatrixError::InvalidElement { row, col, token } =>
+                write!(f, "第 {} 行第 {} 列无法解析: \"{}\"", row + 1, col + 1, token),
+            ParseMatrixError::InvalidHeader(message) => write!(f, "文件头无效: {}", message),
+            ParseMatrixError::MultipleMatrices(count) => write!(f, "输入中有 {} 个矩阵，只能有一个", count),
+            ParseMatrixError::Io(error) => write!(f, "读取文件失败: {}", error),
+        }
+    }
+}
+
+impl From<std::io::Error> for ParseMatrixError {
+    fn from(error: std::io::Error) -> Self {
+        ParseMatrixError::Io(error)
+    }
+}
+
+// 支持三种写法：MATLAB 风格 [1 2; 3 4]，嵌套数组 [[1, 2], [3, 4]]，以及 print_matrix / write_to_file 的方框输出
+impl<T> FromStr for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + FromStr {
+    type Err = ParseMatrixError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let trimmed = text.trim();
+        if trimmed.starts_with('┌') {
+            let mut matrices = parse_boxed_blocks(trimmed)?;
+            return match matrices.len() {
+                0 => Err(ParseMatrixError::Empty),
+                1 => Ok(matrices.remove(0)),
+                count => Err(ParseMatrixError::MultipleMatrices(count)),
+            }
+        }
+        let rows = if trimmed.starts_with('[') {
+            let inner = strip_brackets(trimmed)?;
+            if inner.trim_start().starts_with('[') {
+                split_nested_rows(inner)?
+            } else {
+                split_literal_rows(inner)
+            }
+        } else {
+            split_literal_rows(trimmed)
+        };
+        return build_matrix(&rows);
+    }
+}
+
+impl<T> Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + FromStr {
+    // 读回 write_to_file 追加写入的全部矩阵
+    pub fn read_from_file(file_name: &String) -> Result<Vec<Matrix<T>>, ParseMatrixError> {
+        let text = fs::read_to_string(file_name)?;
+        return parse_boxed_blocks(&text);
+    }
+}
+
+fn strip_brackets(text: &str) -> Result<&str, ParseMatrixError> {
+    let mut depth: i64 = 0;
+    for (index, character) in text.char_indices() {
+        match character {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 || (depth == 0 && index != text.len() - 1) {
+            return Err(ParseMatrixError::UnbalancedBrackets);
+        }
+    }
+    if depth != 0 || !text.ends_with(']') {
+        return Err(ParseMatrixError::UnbalancedBrackets);
+    }
+    return Ok(&text[1..text.len() - 1]);
+}
+
+fn split_elements(row: &str) -> Vec<String> {
+    return row.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_string())
+        .collect();
+}
+
+fn split_literal_rows(text: &str) -> Vec<Vec<String>> {
+    return text.split([';', '\n'])
+        .map(split_elements)
+        .filter(|row| !row.is_empty())
+        .collect();
+}
+
+fn split_nested_rows(text: &str) -> Result<Vec<Vec<String>>, ParseMatrixError> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        if !rest.starts_with('[') {
+            return Err(ParseMatrixError::UnbalancedBrackets);
+        }
+        let end = rest.find(']').ok_or(ParseMatrixError::UnbalancedBrackets)?;
+        if rest[1..end].contains('[') {
+            return Err(ParseMatrixError::UnbalancedBrackets);
+        }
+        rows.push(split_elements(&rest[1..end]));
+        rest = rest[end + 1..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+    return Ok(rows);
+}
+
+fn parse_boxed_blocks<T>(text: &str) -> Result<Vec<Matrix<T>>, ParseMatrixError> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + FromStr {
+    let mut matrices: Vec<Matrix<T>> = Vec::new();
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut inside = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('┌') {
+            inside = true;
+            rows = Vec::new();
+        } else if line.starts_with('└') {
+            if !inside {
+                return Err(ParseMatrixError::UnbalancedBrackets);
+            }
+            inside = false;
+            matrices.push(build_matrix(&rows)?);
+        } else if inside {
+            let content = line.trim_start_matches('│').trim_end_matches('│');
+            rows.push(split_elements(content));
+        }
+    }
+    if inside {
+        return Err(ParseMatrixError::UnbalancedBrackets);
+    }
+    if matrices.is_empty() {
+        return Err(ParseMatrixError::Empty);
+    }
+    return Ok(matrices);
+}
+
+fn build_matrix<T>(rows: &[Vec<String>]) -> Result<Matrix<T>, ParseMatrixError> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + FromStr {
+    if rows.is_empty() || rows[0].is_empty() {
+        return Err(ParseMatrixError::Empty);
+    }
+    let cols = rows[0].len();
+    let mut data: Vec<T> = Vec::with_capacity(rows.len() * cols);
+    for (row_index, row) in rows.iter().enumerate() {
+        if row.len() != cols {
+            return Err(ParseMatrixError::RaggedRow { row: row_index, expected: cols, found: row.len() });
+        }
+        for (col_index, token) in row.iter().enumerate() {
+            let value = token.parse::<T>().map_err(|_| ParseMatrixError::InvalidElement {
+                row: row_index,
+                col: col_index,
+                token: token.clone(),
+            })?;
+            data.push(value);
+        }
+    }
+    return Ok(Matrix::init(rows.len(), cols, data));
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use num_traits::One;
+use crate::matrix::Matrix;
+
+// 置换 σ，保存为 indices[i] = σ(i)。
+// 作用于行时新的第 i 行为原来的第 σ(i) 行，对应的置换矩阵 P 满足 P[i][σ(i)] = 1，即 P A
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Permutation {
+    indices: Vec<usize>,
+}
+
+impl Permutation {
+    pub fn identity(size: usize) -> Permutation {
+        Permutation { indices: (0..size).collect() }
+    }
+
+    // 不是 0..len 的一个排列时返回 None
+    pub fn from_vec(indices: Vec<usize>) -> Option<Permutation> {
+        let mut seen = vec![false; indices.len()];
+        for index in &indices {
+            if *index >= indices.len() || seen[*index] {
+                return None;
+            }
+            seen[*index] = true;
+        }
+        return Some(Permutation { indices });
+    }
+
+    pub fn len(&self) -> usize {
+        return self.indices.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.indices.is_empty();
+    }
+
+    pub fn get(&self, index: usize) -> usize {
+        return self.indices[index];
+    }
+
+    pub fn get_indices(&self) -> &Vec<usize> {
+        return &self.indices;
+    }
+
+    // 交换 σ(a) 与 σ(b)，相当于在已置换的结果上再交换第 a、b 行（列）
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.indices.swap(a, b);
+    }
+
+    pub fn inverse(&self) -> Permutation {
+        let mut indices = vec![0; self.indices.len()];
+        for (index, image) in self.indices.iter().enumerate() {
+            indices[*image] = index;
+        }
+        return Permutation { indices };
+    }
+
+    // 不含不动点的轮换分解，每个轮换从其最小元素开始
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.indices.len()];
+        let mut cycles: Vec<Vec<usize>> = Vec::new();
+        for start in 0..self.indices.len() {
+            if visited[start] {
+                continue;
+            }
+            let mut cycle: Vec<usize> = Vec::new();
+            let mut current = start;
+            while !visited[current] {
+                visited[current] = true;
+                cycle.push(current);
+                current = self.indices[current];
+            }
+            if cycle.len() > 1 {
+                cycles.push(cycle);
+            }
+        }
+        return cycles;
+    }
+
+    // 偶置换为 1，奇置换为 -1
+    pub fn sign(&self) -> i64 {
+        let transpositions: usize = self.cycles().iter().map(|cycle| cycle.len() - 1).sum();
+        return if transpositions.is_multiple_of(2) { 1 } else { -1 };
+    }
+
+    pub fn to_matrix<T>(&self) -> Matrix<T> where
+            T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+        let mut result: Matrix<T> = Matrix::new(self.len(), self.len());
+        for (row, col) in self.indices.iter().enumerate() {
+            result.set(row, *col, T::one());
+        }
+        return result;
+    }
+
+    // 等于 P A，不构造稠密的置换矩阵
+    pub fn apply_to_rows<T>(&self, matrix: &Matrix<T>) -> Matrix<T> where
+            T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+        let mut result = matrix.clone();
+        result.permute_rows(&self.indices);
+        return result;
+    }
+
+    // 新的第 j 列为原来的第 σ(j) 列，等于 A Pᵀ
+    pub fn apply_to_cols<T>(&self, matrix: &Matrix<T>) -> Matrix<T> where
+            T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+        let mut result = matrix.clone();
+        result.permute_cols(&self.indices);
+        return result;
+    }
+}
+
+// 与置换矩阵的乘法一致：(p * q).to_matrix() == p.to_matrix() * q.to_matrix()
+impl Mul for &Permutation {
+    type Output = Permutation;
+
+    fn mul(self, other: Self) -> Self::Output {
+        assert_eq!(self.len(), other.len(), "置换的长度不同");
+        return Permutation { indices: self.indices.iter().map(|index| other.indices[*index]).collect() };
+    }
+}
+
+// 轮换记号，下标从 1 开始，恒等置换输出 ()
+impl fmt::Display for Permutation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cycles = self.cycles();
+        if cycles.is_empty() {
+            return write!(f, "()");
+        }
+        for cycle in cycles {
+            let elements: Vec<String> = cycle.iter().map(|index| (index + 1).to_string()).collect();
+            write!(f, "({})", elements.join(" "))?;
+        }
+        return Ok(());
+    }
+}
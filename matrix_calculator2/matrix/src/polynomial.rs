@@ -0,0 +1,391 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use bigdecimal::BigDecimal;
+use num_traits::{One, Signed, Zero};
+use crate::complex::Complex;
+use crate::polynomial_parser::superscript;
+
+// 求最大公因式时余数系数保留的小数位数，避免 BigDecimal 的位数在迭代中不断增长
+const GCD_SCALE: i64 = 50;
+
+// 复系数多项式，系数按次数从低到高保存。
+// 构造和运算之后都会去掉最高次的零系数，零多项式保存为只有一个零系数
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polynomial {
+    #[cfg_attr(feature = "serde", serde(rename = "parameters"))]
+    coefficients: Vec<Complex>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<Complex>) -> Polynomial {
+        let mut result = Polynomial { coefficients };
+        result.normalize();
+        return result;
+    }
+
+    // 实系数多项式，系数按次数从低到高
+    pub fn from_f64(coefficients: &[f64]) -> Polynomial {
+        return Polynomial::new(coefficients.iter().map(|coefficient| Complex::init(*coefficient, 0.0)).collect());
+    }
+
+    pub fn zero() -> Polynomial {
+        return Polynomial { coefficients: vec![Complex::new()] };
+    }
+
+    pub fn constant(value: &Complex) -> Polynomial {
+        return Polynomial::new(vec![value.clone()]);
+    }
+
+    pub fn get_coefficients(&self) -> &Vec<Complex> {
+        return &self.coefficients;
+    }
+
+    pub fn get_parameters(&self) -> &Vec<Complex> {
+        return &self.coefficients;
+    }
+
+    // 零多项式的次数记为 0
+    pub fn degree(&self) -> usize {
+        return self.coefficients.len() - 1;
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.coefficients.len() == 1 && self.coefficients[0].is_zero();
+    }
+
+    pub fn leading_coefficient(&self) -> &Complex {
+        return self.coefficients.last().unwrap();
+    }
+
+    // 去掉最高次的零系数
+    pub fn normalize(&mut self) {
+        while self.coefficients.len() > 1 && self.coefficients.last().unwrap().is_zero() {
+            self.coefficients.pop();
+        }
+        if self.coefficients.is_empty() {
+            self.coefficients.push(Complex::new());
+        }
+    }
+
+    // 首项系数化为 1，零多项式保持不变
+    pub fn monic(&self) -> Polynomial {
+        if self.is_zero() {
+            return self.clone();
+        }
+        return self / self.leading_coefficient();
+    }
+
+    // Horner 法求值
+    pub fn evaluate(&self, x: &Complex) -> Complex {
+        let mut result = Complex::new();
+        for coefficient in self.coefficients.iter().rev() {
+            result = &(&result * x) + coefficient;
+        }
+        return result;
+    }
+
+    // 复合 self(inner(x))，同样按 Horner 法展开
+    pub fn compose(&self, inner: &Polynomial) -> Polynomial {
+        let mut result = Polynomial::zero();
+        for coefficient in self.coefficients.iter().rev() {
+            result = &(&result * inner) + &Polynomial::constant(coefficient);
+        }
+        return result;
+    }
+
+    // 带余除法，返回 (商, 余数)，余数的次数小于除数的次数。除数为零多项式时 panic
+    pub fn div_rem(&self, divisor: &Polynomial) -> (Polynomial, Polynomial) {
+        assert!(!divisor.is_zero(), "除数不能是零多项式");
+        if self.degree() < divisor.degree() || self.is_zero() {
+            return (Polynomial::zero(), self.clone());
+        }
+        let divisor_degree = divisor.degree();
+        let leading = divisor.leading_coefficient();
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![Complex::new(); self.degree() - divisor_degree + 1];
+        while remainder.len() > divisor_degree {
+            let shift = remainder.len() - 1 - divisor_degree;
+            let factor = remainder.last().unwrap() / leading;
+            // 最高次项按构造应当恰好消去，直接去掉以免舍入误差留下一个很小的首项
+            remainder.pop();
+            for (index, coefficient) in divisor.coefficients[..divisor_degree].iter().enumerate() {
+                remainder[shift + index] = &remainder[shift + index] - &(&factor * coefficient);
+            }
+            quotient[shift] = factor;
+        }
+        return (Polynomial::new(quotient), Polynomial::new(remainder));
+    }
+
+    // 综合除法除以 x - root，舍去余数。root 是根时余数就是 self(root)，应当接近零
+    pub fn deflate(&self, root: &Complex) -> Polynomial {
+        if self.coefficients.len() == 1 {
+            return Polynomial::zero();
+        }
+        let mut quotient: Vec<Complex> = vec![Complex::new(); self.coefficients.len() - 1];
+        let mut carry = Complex::new();
+        for degree in (1..self.coefficients.len()).rev() {
+            carry = &self.coefficients[degree] + &(&carry * root);
+            quotient[degree - 1] = carry.clone();
+        }
+        return Polynomial::new(quotient);
+    }
+
+    // order 阶导数，x^n 的系数乘以 n(n-1)...(n-order+1)；order 超过次数时为零多项式
+    pub fn derivative(&self, order: usize) -> Polynomial {
+        if order >= self.coefficients.len() {
+            return Polynomial::zero();
+        }
+        return Polynomial::new(self.coefficients.iter().enumerate().skip(order)
+            .map(|(degree, coefficient)| {
+                let factor = (degree - order + 1..=degree).fold(BigDecimal::one(), |product, k| product * BigDecimal::from(k as u64));
+                coefficient * &Complex::from_big_decimal(&factor, &BigDecimal::zero())
+            })
+            .collect());
+    }
+
+    // 常数项为 constant 的原函数
+    pub fn antiderivative(&self, constant: &Complex) -> Polynomial {
+        let mut coefficients = vec![constant.clone()];
+        for (degree, coefficient) in self.coefficients.iter().enumerate() {
+            coefficients.push(coefficient / &Complex::from_big_decimal(&BigDecimal::from(degree as u64 + 1), &BigDecimal::zero()));
+        }
+        return Polynomial::new(coefficients);
+    }
+
+    // 实区间 [lower, upper] 上的定积分 F(upper) - F(lower)，lower > upper 时结果变号
+    pub fn integrate(&self, lower: &BigDecimal, upper: &BigDecimal) -> Complex {
+        let primitive = self.antiderivative(&Complex::new());
+        let zero = BigDecimal::zero();
+        return &primitive.evaluate(&Complex::from_big_decimal(upper, &zero)) - &primitive.evaluate(&Complex::from_big_decimal(lower, &zero));
+    }
+
+    // Taylor 平移 p(x + shift)，结果的系数是 p 在 shift 处的各阶导数除以阶乘。
+    // 反复做综合除法，只用 O(n²) 次乘法
+    pub fn taylor_shift(&self, shift: &Complex) -> Polynomial {
+        let mut coefficients = self.coefficients.clone();
+        let size = coefficients.len();
+        for start in 0..size {
+            for index in (start..size - 1).rev() {
+                coefficients[index] = &coefficients[index] + &(shift * &coefficients[index + 1]);
+            }
+        }
+        return Polynomial::new(coefficients);
+    }
+
+    // p(factor · x)，x^n 的系数乘以 factor^n
+    pub fn scale(&self, factor: &Complex) -> Polynomial {
+        let mut power = Complex::init(1.0, 0.0);
+        let mut coefficients: Vec<Complex> = Vec::with_capacity(self.coefficients.len());
+        for coefficient in &self.coefficients {
+            coefficients.push(coefficient * &power);
+            power = &power * factor;
+        }
+        return Polynomial::new(coefficients);
+    }
+
+    // 系数模的最大值
+    pub fn max_norm(&self) -> BigDecimal {
+        return self.coefficients.iter().map(|coefficient| coefficient.abs()).max().unwrap();
+    }
+
+    // 去掉模不超过 tolerance 的最高次系数，用来清除消元后残留的舍入误差。
+    // 所有系数都可以忽略时得到零多项式
+    pub fn trim(&self, tolerance: &BigDecimal) -> Polynomial {
+        let mut coefficients = self.coefficients.clone();
+        while coefficients.last().is_some_and(|coefficient| coefficient.abs() <= *tolerance) {
+            coefficients.pop();
+        }
+        return Polynomial::new(coefficients);
+    }
+
+    // 首一的最大公因式。系数是 BigDecimal，除法并不精确，
+    // 所以每一步的余数都去掉相对被除数模小于 tolerance 的首项，全部去掉时视为整除
+    pub fn gcd(&self, other: &Polynomial, tolerance: &BigDecimal) -> Polynomial {
+        let (mut a, mut b) = if self.degree() >= other.degree() {
+            (self.monic(), other.monic())
+        } else {
+            (other.monic(), self.monic())
+        };
+        while !b.is_zero() {
+            let scale = tolerance * a.max_norm();
+            let remainder = (&a % &b).trim(&scale).round_coefficients(GCD_SCALE);
+            a = b;
+            b = remainder.monic();
+        }
+        return a.monic();
+    }
+
+    // Yun 算法求无平方分解 self = c * a_1 * a_2^2 * ... * a_k^k，
+    // 返回首一且次数至少为 1 的 (a_i, i)，按重数从小到大排列
+    pub fn square_free_decomposition(&self, tolerance: &BigDecimal) -> Vec<(Polynomial, usize)> {
+        let mut factors: Vec<(Polynomial, usize)> = Vec::new();
+        if self.degree() < 1 {
+            return factors;
+        }
+        let monic = self.monic();
+        let derivative = monic.derivative(1);
+        let common = monic.gcd(&derivative, tolerance);
+        let mut b = &monic / &common;
+        let mut c = &derivative / &common;
+        let mut d = &c - &b.derivative(1);
+        let mut multiplicity = 1;
+        while b.degree() >= 1 && multiplicity <= self.degree() {
+            let a = b.gcd(&d.trim(&(tolerance * c.max_norm())), tolerance);
+            b = &b / &a;
+            c = &d / &a;
+            d = &c - &b.derivative(1);
+            if a.degree() >= 1 {
+                factors.push((a, multiplicity));
+            }
+            multiplicity += 1;
+        }
+        return factors;
+    }
+
+    pub(crate) fn round_coefficients(&self, scale: i64) -> Polynomial {
+        return Polynomial::new(self.coefficients.iter()
+            .map(|coefficient| coefficient.round(scale))
+            .collect());
+    }
+}
+
+impl Default for Polynomial {
+    fn default() -> Self {
+        Polynomial::zero()
+    }
+}
+
+// 按次数从高到低输出，如 2x^3 + 6x^2 - 2x - 1，复系数写在括号里，如 (1 + 2i)x^2。
+// 输出可以被 from_str 读回；{:.3} 把系数四舍五入到三位小数，{:#} 用上标表示次数，如 2x³
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let round = |value: &BigDecimal| match f.precision() {
+            Some(precision) => value.round(precision as i64),
+            None => value.clone(),
+        };
+        let mut text = String::new();
+        for (degree, coefficient) in self.coefficients.iter().enumerate().rev() {
+            let (real, imaginary) = (round(&coefficient.real), round(&coefficient.imaginary));
+            let (negative, magnitude) = if imaginary.is_zero() {
+                if real.is_zero() {
+                    continue;
+                }
+                let magnitude = real.abs();
+                (real.is_negative(), if magnitude.is_one() && degree > 0 { String::new() } else { decimal(&magnitude) })
+            } else if real.is_zero() {
+                let magnitude = imaginary.abs();
+                (imaginary.is_negative(), if magnitude.is_one() { "i".to_string() } else { format!("{}i", decimal(&magnitude)) })
+            } else {
+                let sign = if imaginary.is_negative() { "-" } else { "+" };
+                (false, format!("({} {} {}i)", decimal(&real), sign, decimal(&imaginary.abs())))
+            };
+            let variable = match degree {
+                0 => String::new(),
+                1 => "x".to_string(),
+                _ if f.alternate() => format!("x{}", superscript(degree)),
+                _ => format!("x^{}", degree),
+            };
+            let sign = match (text.is_empty(), negative) {
+                (true, true) => "-",
+                (true, false) => "",
+                (false, true) => " - ",
+                (false, false) => " + ",
+            };
+            text.push_str(&format!("{}{}{}", sign, magnitude, variable));
+        }
+        if text.is_empty() {
+            text.push('0');
+        }
+        return write!(f, "{}", text);
+    }
+}
+
+// 去掉末尾多余的 0，也不使用科学计数法
+pub(crate) fn decimal(value: &BigDecimal) -> String {
+    let normalized = value.normalized();
+    if normalized.fractional_digit_count() < 0 {
+        return normalized.with_scale(0).to_string();
+    }
+    return normalized.to_string();
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result: Vec<Complex> = vec![Complex::new(); self.coefficients.len().max(rhs.coefficients.len())];
+        for (index, coefficient) in self.coefficients.iter().enumerate() {
+            result[index] = coefficient.clone();
+        }
+        for (index, coefficient) in rhs.coefficients.iter().enumerate() {
+            result[index] = &result[index] + coefficient;
+        }
+        return Polynomial::new(result);
+    }
+}
+
+impl Sub for &Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        return self + &(-rhs);
+    }
+}
+
+impl Neg for &Polynomial {
+    type Output = Polynomial;
+
+    fn neg(self) -> Self::Output {
+        let zero = Complex::new();
+        return Polynomial::new(self.coefficients.iter().map(|coefficient| &zero - coefficient).collect());
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut result: Vec<Complex> = vec![Complex::new(); self.coefficients.len() + rhs.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in rhs.coefficients.iter().enumerate() {
+                result[i + j] = &result[i + j] + &(a * b);
+            }
+        }
+        return Polynomial::new(result);
+    }
+}
+
+impl Mul<&Complex> for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, rhs: &Complex) -> Self::Output {
+        return Polynomial::new(self.coefficients.iter().map(|coefficient| coefficient * rhs).collect());
+    }
+}
+
+impl Div<&Complex> for &Polynomial {
+    type Output = Polynomial;
+
+    fn div(self, rhs: &Complex) -> Self::Output {
+        return Polynomial::new(self.coefficients.iter().map(|coefficient| coefficient / rhs).collect());
+    }
+}
+
+// 多项式除法的商
+impl Div for &Polynomial {
+    type Output = Polynomial;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        return self.div_rem(rhs).0;
+    }
+}
+
+// 多项式除法的余数
+impl Rem for &Polynomial {
+    type Output = Polynomial;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        return self.div_rem(rhs).1;
+    }
+}
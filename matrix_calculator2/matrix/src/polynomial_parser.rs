@@ -0,0 +1,269 @@
+use std::fmt;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use num_traits::Zero;
+use crate::complex::Complex;
+use crate::polynomial::Polynomial;
+
+// 允许的最高次数，过大的次数会让系数向量申请不下来的内存
+pub const MAX_DEGREE: usize = 10000;
+
+// 位置按字符计数，从 0 开始
+#[derive(Clone, PartialEq, Debug)]
+pub enum ParsePolynomialError {
+    Empty,
+    UnexpectedCharacter { position: usize, found: char },
+    UnexpectedEnd,
+    InvalidNumber { position: usize, token: String },
+    MixedVariables { expected: char, found: char },
+    MultipleEquals { position: usize },
+    ExponentTooLarge { position: usize, limit: usize },
+}
+
+impl fmt::Display for ParsePolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePolynomialError::Empty => write!(f, "多项式为空"),
+            ParsePolynomialError::UnexpectedCharacter { position, found } =>
+                write!(f, "第 {} 个字符 '{}' 无法解析", position + 1, found),
+            ParsePolynomialError::UnexpectedEnd => write!(f, "多项式不完整"),
+            ParsePolynomialError::InvalidNumber { position, token } =>
+                write!(f, "第 {} 个字符开始的 \"{}\" 不是合法的数字", position + 1, token),
+            ParsePolynomialError::MixedVariables { expected, found } =>
+                write!(f, "只能有一个未知数，已经使用了 {}，又出现了 {}", expected, found),
+            ParsePolynomialError::MultipleEquals { position } => write!(f, "第 {} 个字符处出现了第二个等号", position + 1),
+            ParsePolynomialError::ExponentTooLarge { position, limit } =>
+                write!(f, "第 {} 个字符开始的次数超过了上限 {}", position + 1, limit),
+        }
+    }
+}
+
+// 支持 "2x^3 + 6x^2 - 2x - 1"、省略的系数 "x^2 - x"、括号里的复数系数 "(1+2i)x^2"、
+// 纯虚数 "3i"、上标 "x³" 以及方程 "x^2 = 2x + 1"（整理为左边减右边）。
+// 未知数可以是 i 以外的任意一个字母，但同一个多项式中只能出现一种
+impl FromStr for Polynomial {
+    type Err = ParsePolynomialError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { chars: text.chars().collect(), position: 0, variable: None };
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return Err(ParsePolynomialError::Empty);
+        }
+        let mut result = parser.side()?;
+        if parser.peek() == Some('=') {
+            parser.position += 1;
+            let right = parser.side()?;
+            if parser.peek() == Some('=') {
+                return Err(ParsePolynomialError::MultipleEquals { position: parser.position });
+            }
+            result = &result - &right;
+        }
+        return match parser.peek() {
+            Some(found) => Err(ParsePolynomialError::UnexpectedCharacter { position: parser.position, found }),
+            None => Ok(result),
+        };
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    variable: Option<char>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        return self.chars.get(self.position).copied();
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn unexpected(&self) -> ParsePolynomialError {
+        return match self.peek() {
+            Some(found) => ParsePolynomialError::UnexpectedCharacter { position: self.position, found },
+            None => ParsePolynomialError::UnexpectedEnd,
+        };
+    }
+
+    // 等号一侧：带符号的若干项之和
+    fn side(&mut self) -> Result<Polynomial, ParsePolynomialError> {
+        let mut coefficients: Vec<Complex> = Vec::new();
+        self.skip_whitespace();
+        let mut negative = self.sign();
+        loop {
+            let (coefficient, degree) = self.term()?;
+            if coefficients.len() <= degree {
+                coefficients.resize(degree + 1, Complex::new());
+            }
+            coefficients[degree] = if negative {
+                &coefficients[degree] - &coefficient
+            } else {
+                &coefficients[degree] + &coefficient
+            };
+            self.skip_whitespace();
+            match self.peek() {
+                Some('+') | Some('-') => negative = self.sign(),
+                _ => break,
+            }
+        }
+        return Ok(Polynomial::new(coefficients));
+    }
+
+    // 读掉一个可选的正负号，返回是否为负
+    fn sign(&mut self) -> bool {
+        let negative = match self.peek() {
+            Some('-') => true,
+            Some('+') => false,
+            _ => return false,
+        };
+        self.position += 1;
+        self.skip_whitespace();
+        return negative;
+    }
+
+    // 一项：系数、未知数和次数都可以省略，但系数和未知数不能同时省略
+    fn term(&mut self) -> Result<(Complex, usize), ParsePolynomialError> {
+        self.skip_whitespace();
+        let coefficient = match self.peek() {
+            Some('(') => Some(self.parenthesized()?),
+            Some(c) if c.is_ascii_digit() || c == '.' || c == 'i' => Some(self.number()?),
+            _ => None,
+        };
+        self.skip_whitespace();
+        let explicit_product = coefficient.is_some() && self.peek() == Some('*') && self.chars.get(self.position + 1) != Some(&'*');
+        if explicit_product {
+            self.position += 1;
+            self.skip_whitespace();
+        }
+        let degree = match self.peek() {
+            Some(c) if c.is_alphabetic() && c != 'i' => {
+                self.use_variable(c)?;
+                self.position += 1;
+                self.exponent()?
+            }
+            _ if coefficient.is_none() || explicit_product => return Err(self.unexpected()),
+            _ => 0,
+        };
+        return Ok((coefficient.unwrap_or(Complex::init(1.0, 0.0)), degree));
+    }
+
+    fn use_variable(&mut self, found: char) -> Result<(), ParsePolynomialError> {
+        return match self.variable {
+            Some(expected) if expected != found => Err(ParsePolynomialError::MixedVariables { expected, found }),
+            _ => {
+                self.variable = Some(found);
+                Ok(())
+            }
+        };
+    }
+
+    // 未知数之后的次数：^n、**n 或者上标，省略时为 1
+    fn exponent(&mut self) -> Result<usize, ParsePolynomialError> {
+        let mut superscript = String::new();
+        let start = self.position;
+        while let Some(digit) = self.peek().and_then(superscript_digit) {
+            superscript.push(digit);
+            self.position += 1;
+        }
+        if !superscript.is_empty() {
+            let token: String = self.chars[start..self.position].iter().collect();
+            let degree = superscript.parse().map_err(|_| ParsePolynomialError::InvalidNumber { position: start, token })?;
+            return check_degree(degree, start);
+        }
+        self.skip_whitespace();
+        match (self.peek(), self.chars.get(self.position + 1)) {
+            (Some('^'), _) => self.position += 1,
+            (Some('*'), Some('*')) => self.position += 2,
+            _ => return Ok(1),
+        }
+        self.skip_whitespace();
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(self.unexpected());
+        }
+        let token: String = self.chars[start..self.position].iter().collect();
+        let degree = token.parse().map_err(|_| ParsePolynomialError::InvalidNumber { position: start, token })?;
+        return check_degree(degree, start);
+    }
+
+    // 实数、带 i 的虚数或者单独的 i
+    fn number(&mut self) -> Result<Complex, ParsePolynomialError> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.position += 1;
+        }
+        let token: String = self.chars[start..self.position].iter().collect();
+        let value = if token.is_empty() {
+            BigDecimal::from(1)
+        } else {
+            BigDecimal::from_str(&token).map_err(|_| ParsePolynomialError::InvalidNumber { position: start, token })?
+        };
+        if self.peek() == Some('i') {
+            self.position += 1;
+            return Ok(Complex::from_big_decimal(&BigDecimal::zero(), &value));
+        }
+        return Ok(Complex::from_big_decimal(&value, &BigDecimal::zero()));
+    }
+
+    // 括号里的复数，如 (1+2i)、(-i)、(0.5 - 3i)
+    fn parenthesized(&mut self) -> Result<Complex, ParsePolynomialError> {
+        self.position += 1;
+        self.skip_whitespace();
+        let mut result = Complex::new();
+        let mut negative = self.sign();
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_digit() || c == '.' || c == 'i' => {}
+                _ => return Err(self.unexpected()),
+            }
+            let value = self.number()?;
+            result = if negative { &result - &value } else { &result + &value };
+            self.skip_whitespace();
+            match self.peek() {
+                Some(')') => break,
+                Some('+') | Some('-') => negative = self.sign(),
+                _ => return Err(self.unexpected()),
+            }
+        }
+        self.position += 1;
+        return Ok(result);
+    }
+}
+
+// 次数超过 MAX_DEGREE 时报错，position 为次数开始的位置
+fn check_degree(degree: usize, position: usize) -> Result<usize, ParsePolynomialError> {
+    if degree > MAX_DEGREE {
+        return Err(ParsePolynomialError::ExponentTooLarge { position, limit: MAX_DEGREE });
+    }
+    return Ok(degree);
+}
+
+fn superscript_digit(c: char) -> Option<char> {
+    return match c {
+        '⁰' => Some('0'),
+        '¹' => Some('1'),
+        '²' => Some('2'),
+        '³' => Some('3'),
+        '⁴' => Some('4'),
+        '⁵' => Some('5'),
+        '⁶' => Some('6'),
+        '⁷' => Some('7'),
+        '⁸' => Some('8'),
+        '⁹' => Some('9'),
+        _ => None,
+    };
+}
+
+pub(crate) fn superscript(exponent: usize) -> String {
+    return exponent.to_string().chars()
+        .map(|digit| ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'][digit.to_digit(10).unwrap() as usize])
+        .collect();
+}
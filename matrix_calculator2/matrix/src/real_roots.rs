@@ -0,0 +1,371 @@
+use std::fmt;
+use bigdecimal::BigDecimal;
+use num_traits::{Signed, Zero};
+use crate::complex::Complex;
+use crate::polynomial::Polynomial;
+
+// 实系数多项式的实根隔离。系数按次数从低到高保存为 BigDecimal，
+// 这里只用加减乘和伪除法，全部是精确运算，因此根的个数和隔离区间都是有保证的
+
+// 根所在的区间。lower < upper 时为开区间 (lower, upper)，其中恰好有一个根；lower == upper 时根恰好等于它
+#[derive(Clone, PartialEq, Debug)]
+pub struct RootInterval {
+    pub lower: BigDecimal,
+    pub upper: BigDecimal,
+}
+
+impl RootInterval {
+    pub fn is_exact(&self) -> bool {
+        return self.lower == self.upper;
+    }
+
+    pub fn width(&self) -> BigDecimal {
+        return &self.upper - &self.lower;
+    }
+
+    pub fn midpoint(&self) -> BigDecimal {
+        return midpoint(&self.lower, &self.upper);
+    }
+}
+
+impl fmt::Display for RootInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_exact() {
+            return write!(f, "{}", self.lower);
+        }
+        return write!(f, "({}, {})", self.lower, self.upper);
+    }
+}
+
+// Sturm 序列 p_0 = p, p_1 = p', p_{k+1} = -prem(p_{k-1}, p_k)。
+// 伪余式乘的是除式首项系数绝对值的幂，不改变符号，所以仍然是 Sturm 序列，最后一项是 p 与 p' 的最大公因式
+#[derive(Clone, Debug)]
+pub struct SturmSequence {
+    polynomials: Vec<Vec<BigDecimal>>,
+}
+
+impl SturmSequence {
+    fn new(coefficients: Vec<BigDecimal>) -> SturmSequence {
+        let mut polynomials = vec![coefficients.clone(), derivative(&coefficients)];
+        if is_zero(&polynomials[1]) {
+            polynomials.pop();
+        }
+        while polynomials.len() >= 2 {
+            let remainder = pseudo_div_rem(&polynomials[polynomials.len() - 2], &polynomials[polynomials.len() - 1]).1;
+            if is_zero(&remainder) {
+                break;
+            }
+            polynomials.push(remainder.iter().map(|coefficient| -coefficient).collect());
+        }
+        return SturmSequence { polynomials };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.polynomials.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.polynomials.is_empty();
+    }
+
+    pub fn get_polynomials(&self) -> Vec<Polynomial> {
+        return self.polynomials.iter().map(|coefficients| to_polynomial(coefficients)).collect();
+    }
+
+    // 序列在 x 处的变号次数，零值不计
+    pub fn sign_variations(&self, x: &BigDecimal) -> usize {
+        return variations(self.polynomials.iter().map(|coefficients| evaluate(coefficients, x)));
+    }
+
+    // (lower, upper] 中互不相同的实根个数
+    pub fn count_roots(&self, lower: &BigDecimal, upper: &BigDecimal) -> usize {
+        if lower >= upper {
+            return 0;
+        }
+        return self.sign_variations(lower).saturating_sub(self.sign_variations(upper));
+    }
+
+    // 全部互不相同的实根个数，用 ±∞ 处首项系数的符号计算
+    pub fn count_all_roots(&self) -> usize {
+        let at_negative_infinity = self.polynomials.iter().map(|coefficients| {
+            let leading = coefficients.last().unwrap().clone();
+            if (coefficients.len() - 1) % 2 == 1 { -leading } else { leading }
+        });
+        let at_positive_infinity = self.polynomials.iter().map(|coefficients| coefficients.last().unwrap().clone());
+        return variations(at_negative_infinity).saturating_sub(variations(at_positive_infinity));
+    }
+}
+
+impl Polynomial {
+    // 实系数多项式的 Sturm 序列，系数有虚部或者是零多项式时返回 None
+    pub fn sturm_sequence(&self) -> Option<SturmSequence> {
+        let coefficients = real_coefficients(self)?;
+        return Some(SturmSequence::new(coefficients));
+    }
+
+    // (lower, upper] 中互不相同的实根个数
+    pub fn count_real_roots(&self, lower: &BigDecimal, upper: &BigDecimal) -> Option<usize> {
+        return Some(self.sturm_sequence()?.count_roots(lower, upper));
+    }
+
+    // Vincent–Collins–Akritas 二分法：在无平方部分上用 Descartes 符号法则判断区间中根的个数，
+    // 变号数为 0 时没有根，为 1 时恰好一个根，否则二分。返回按从小到大排列、互不相交的隔离区间
+    pub fn isolate_real_roots(&self) -> Option<Vec<RootInterval>> {
+        let square_free = square_free_part(&real_coefficients(self)?);
+        return Some(isolate(&square_free));
+    }
+
+    // 隔离之后把每个区间细化到宽度不超过 10^-digits
+    pub fn real_roots(&self, digits: i64) -> Option<Vec<RootInterval>> {
+        let square_free = square_free_part(&real_coefficients(self)?);
+        return Some(isolate(&square_free).iter().map(|interval| refine(&square_free, interval, digits)).collect());
+    }
+
+    // 用 Illinois 法（改进的试位法）细化 isolate_real_roots 返回的区间，宽度不够小时穿插二分
+    pub fn refine_real_root(&self, interval: &RootInterval, digits: i64) -> Option<RootInterval> {
+        let square_free = square_free_part(&real_coefficients(self)?);
+        return Some(refine(&square_free, interval, digits));
+    }
+}
+
+fn real_coefficients(polynomial: &Polynomial) -> Option<Vec<BigDecimal>> {
+    if polynomial.is_zero() || polynomial.get_coefficients().iter().any(|coefficient| !coefficient.get_imaginary().is_zero()) {
+        return None;
+    }
+    return Some(polynomial.get_coefficients().iter().map(|coefficient| coefficient.get_real().clone()).collect());
+}
+
+fn to_polynomial(coefficients: &[BigDecimal]) -> Polynomial {
+    return Polynomial::new(coefficients.iter().map(|coefficient| Complex::from_big_decimal(coefficient, &BigDecimal::zero())).collect());
+}
+
+fn is_zero(coefficients: &[BigDecimal]) -> bool {
+    return coefficients.iter().all(|coefficient| coefficient.is_zero());
+}
+
+fn trim(coefficients: &mut Vec<BigDecimal>) {
+    while coefficients.len() > 1 && coefficients.last().unwrap().is_zero() {
+        coefficients.pop();
+    }
+}
+
+fn evaluate(coefficients: &[BigDecimal], x: &BigDecimal) -> BigDecimal {
+    let mut result = BigDecimal::zero();
+    for coefficient in coefficients.iter().rev() {
+        result = result * x + coefficient;
+    }
+    return result;
+}
+
+fn derivative(coefficients: &[BigDecimal]) -> Vec<BigDecimal> {
+    if coefficients.len() == 1 {
+        return vec![BigDecimal::zero()];
+    }
+    return coefficients.iter().enumerate().skip(1).map(|(degree, coefficient)| coefficient * BigDecimal::from(degree as u64)).collect();
+}
+
+fn multiply(lhs: &[BigDecimal], rhs: &[BigDecimal]) -> Vec<BigDecimal> {
+    let mut result = vec![BigDecimal::zero(); lhs.len() + rhs.len() - 1];
+    for (i, a) in lhs.iter().enumerate() {
+        for (j, b) in rhs.iter().enumerate() {
+            result[i + j] += a * b;
+        }
+    }
+    return result;
+}
+
+// 伪除法：|lc(b)|^k a = q b + r，deg r < deg b，只用乘法和减法，结果是精确的
+fn pseudo_div_rem(a: &[BigDecimal], b: &[BigDecimal]) -> (Vec<BigDecimal>, Vec<BigDecimal>) {
+    let mut remainder = a.to_vec();
+    trim(&mut remainder);
+    if remainder.len() < b.len() {
+        return (vec![BigDecimal::zero()], remainder);
+    }
+    let leading = b.last().unwrap();
+    let scale = leading.abs();
+    let negative = leading.is_negative();
+    let mut quotient = vec![BigDecimal::zero(); remainder.len() - b.len() + 1];
+    while remainder.len() >= b.len() && !is_zero(&remainder) {
+        let shift = remainder.len() - b.len();
+        let factor = if negative { -remainder.last().unwrap() } else { remainder.last().unwrap().clone() };
+        for coefficient in quotient.iter_mut() {
+            *coefficient *= &scale;
+        }
+        quotient[shift] += &factor;
+        for coefficient in remainder.iter_mut() {
+            *coefficient *= &scale;
+        }
+        for (index, coefficient) in b.iter().enumerate() {
+            remainder[shift + index] -= &factor * coefficient;
+        }
+        // 首项按构造恰好消去
+        remainder.pop();
+        if remainder.is_empty() {
+            remainder.push(BigDecimal::zero());
+        }
+        trim(&mut remainder);
+    }
+    return (quotient, remainder);
+}
+
+// p / gcd(p, p')，与 p 只差一个正的常数倍
+fn square_free_part(coefficients: &[BigDecimal]) -> Vec<BigDecimal> {
+    let sequence = SturmSequence::new(coefficients.to_vec());
+    let gcd = sequence.polynomials.last().unwrap();
+    if gcd.len() == 1 {
+        return coefficients.to_vec();
+    }
+    let mut quotient = pseudo_div_rem(coefficients, gcd).0;
+    trim(&mut quotient);
+    return quotient;
+}
+
+fn variations(values: impl Iterator<Item = BigDecimal>) -> usize {
+    let mut count = 0;
+    let mut previous: Option<bool> = None;
+    for value in values {
+        if value.is_zero() {
+            continue;
+        }
+        let negative = value.is_negative();
+        if previous.is_some_and(|previous| previous != negative) {
+            count += 1;
+        }
+        previous = Some(negative);
+    }
+    return count;
+}
+
+fn midpoint(lower: &BigDecimal, upper: &BigDecimal) -> BigDecimal {
+    return (lower + upper) * BigDecimal::new(5.into(), 1);
+}
+
+// (x + 1)^n p((a x + b) / (x + 1)) 的系数变号数，它是 p 在 (a, b) 中根的个数的上界，且奇偶性相同
+fn descartes_bound(coefficients: &[BigDecimal], lower: &BigDecimal, upper: &BigDecimal) -> usize {
+    let degree = coefficients.len() - 1;
+    let one = BigDecimal::from(1);
+    let mut mobius_powers: Vec<Vec<BigDecimal>> = vec![vec![one.clone()]];
+    let mut shift_powers: Vec<Vec<BigDecimal>> = vec![vec![one.clone()]];
+    for index in 0..degree {
+        mobius_powers.push(multiply(&mobius_powers[index], &[upper.clone(), lower.clone()]));
+        shift_powers.push(multiply(&shift_powers[index], &[one.clone(), one.clone()]));
+    }
+    let mut transformed = vec![BigDecimal::zero(); degree + 1];
+    for (power, coefficient) in coefficients.iter().enumerate() {
+        if coefficient.is_zero() {
+            continue;
+        }
+        for (index, value) in multiply(&mobius_powers[power], &shift_powers[degree - power]).iter().enumerate() {
+            transformed[index] += coefficient * value;
+        }
+    }
+    return variations(transformed.into_iter());
+}
+
+// 所有实根的模都小于 1 + max |a_i / a_n|，取一个更大的整数作为初始区间
+fn root_bound(coefficients: &[BigDecimal]) -> BigDecimal {
+    let leading = coefficients.last().unwrap().abs();
+    let largest = coefficients.iter().map(|coefficient| coefficient.abs()).max().unwrap();
+    return (largest / leading).round(0) + BigDecimal::from(2);
+}
+
+fn isolate(square_free: &[BigDecimal]) -> Vec<RootInterval> {
+    let mut result: Vec<RootInterval> = Vec::new();
+    if square_free.len() < 2 {
+        return result;
+    }
+    let bound = root_bound(square_free);
+    let mut pending = vec![(-&bound, bound)];
+    while let Some((lower, upper)) = pending.pop() {
+        match descartes_bound(square_free, &lower, &upper) {
+            0 => {}
+            1 => result.push(RootInterval { lower, upper }),
+            _ => {
+                let middle = midpoint(&lower, &upper);
+                if evaluate(square_free, &middle).is_zero() {
+                    result.push(RootInterval { lower: middle.clone(), upper: middle.clone() });
+                }
+                pending.push((lower, middle.clone()));
+                pending.push((middle, upper));
+            }
+        }
+    }
+    result.sort_by(|a, b| a.lower.cmp(&b.lower).then(a.upper.cmp(&b.upper)));
+    return result.iter().map(|interval| tighten(square_free, interval)).collect();
+}
+
+// 二分时的分点可能恰好是相邻区间的根，此时把这个端点向内收缩，保证细化时两端的函数值异号
+fn tighten(square_free: &[BigDecimal], interval: &RootInterval) -> RootInterval {
+    if interval.is_exact() {
+        return interval.clone();
+    }
+    let (mut lower, mut upper) = (interval.lower.clone(), interval.upper.clone());
+    while evaluate(square_free, &lower).is_zero() || evaluate(square_free, &upper).is_zero() {
+        let middle = midpoint(&lower, &upper);
+        if evaluate(square_free, &middle).is_zero() {
+            return RootInterval { lower: middle.clone(), upper: middle };
+        }
+        if descartes_bound(square_free, &lower, &middle) == 1 {
+            upper = middle;
+        } else {
+            lower = middle;
+        }
+    }
+    return RootInterval { lower, upper };
+}
+
+fn refine(square_free: &[BigDecimal], interval: &RootInterval, digits: i64) -> RootInterval {
+    let tolerance = BigDecimal::new(1.into(), digits);
+    let (mut lower, mut upper) = (interval.lower.clone(), interval.upper.clone());
+    let (mut lower_value, mut upper_value) = (evaluate(square_free, &lower), evaluate(square_free, &upper));
+    // 上一次保留的是哪一端，连续两次保留同一端时把它的函数值减半
+    let mut kept_lower: Option<bool> = None;
+    while &upper - &lower > tolerance {
+        let width = &upper - &lower;
+        let denominator = &upper_value - &lower_value;
+        let mut point = if denominator.is_zero() {
+            midpoint(&lower, &upper)
+        } else {
+            ((&lower * &upper_value - &upper * &lower_value) / denominator).round(digits + 2)
+        };
+        if point <= lower || point >= upper {
+            point = midpoint(&lower, &upper);
+        }
+        let value = evaluate(square_free, &point);
+        if value.is_zero() {
+            return RootInterval { lower: point.clone(), upper: point };
+        }
+        if value.is_negative() == lower_value.is_negative() {
+            lower = point;
+            lower_value = value;
+            if kept_lower == Some(false) {
+                upper_value *= BigDecimal::new(5.into(), 1);
+            }
+            kept_lower = Some(false);
+        } else {
+            upper = point;
+            upper_value = value;
+            if kept_lower == Some(true) {
+                lower_value *= BigDecimal::new(5.into(), 1);
+            }
+            kept_lower = Some(true);
+        }
+        // 区间没有缩小一半时补一次二分，保证宽度至少线性收敛
+        if (&upper - &lower) * BigDecimal::from(2) > width {
+            let middle = midpoint(&lower, &upper);
+            let value = evaluate(square_free, &middle);
+            if value.is_zero() {
+                return RootInterval { lower: middle.clone(), upper: middle };
+            }
+            if value.is_negative() == evaluate(square_free, &lower).is_negative() {
+                lower = middle;
+                lower_value = value;
+            } else {
+                upper = middle;
+                upper_value = value;
+            }
+            kept_lower = None;
+        }
+    }
+    return RootInterval { lower, upper };
+}
@@ -0,0 +1,219 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use num_traits::Signed;
+use crate::polynomial::Polynomial;
+use crate::expression::{parse_program, Interpreter, Statement};
+use crate::matrix::Matrix;
+
+pub use crate::expression::Value;
+
+const HELP: &str = "\
+命令：
+  A = [1 2; 3 4]        定义矩阵（也支持 [[1, 2], [3, 4]]）
+  z = 1 + 2i            定义复数
+  inv(A) * B'           求值表达式，' 表示转置，.* 表示逐元素乘法
+  det(A) rref(A) transpose(A) exp(z) abs(z) conj(z)
+  p = 2x^3+6x^2-2x-1    定义多项式，eval(p, 2) 求值
+  roots(p)              求多项式的根
+  vars                  列出已定义的变量
+  history / !n          查看历史 / 重新执行第 n 条
+  save 文件 / load 文件  保存或读取变量
+  help / exit";
+
+pub enum Output {
+    Value(Value),
+    Assigned(String, Value),
+    Text(String),
+    Nothing,
+    Exit,
+}
+
+pub struct Session {
+    interpreter: Interpreter,
+    history: Vec<String>,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session {
+            interpreter: Interpreter::new(),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<&Value> {
+        return self.interpreter.get_variable(name);
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<Output, String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(Output::Nothing);
+        }
+        if let Some(index) = line.strip_prefix('!') {
+            let index: usize = index.trim().parse().map_err(|_| format!("无效的历史编号: {}", index))?;
+            let entry = self.history.get(index.wrapping_sub(1)).cloned().ok_or(format!("没有第 {} 条历史", index))?;
+            return self.execute(&entry);
+        }
+        self.history.push(line.to_string());
+
+        let (command, argument) = match line.split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line, ""),
+        };
+        match command {
+            "exit" | "quit" => return Ok(Output::Exit),
+            "help" => return Ok(Output::Text(HELP.to_string())),
+            "history" => {
+                let lines: Vec<String> = self.history.iter().enumerate().map(|(index, entry)| format!("{:>4}  {}", index + 1, entry)).collect();
+                return Ok(Output::Text(lines.join("\n")));
+            }
+            "vars" => {
+                let variables = self.interpreter.get_variables();
+                let mut names: Vec<&String> = variables.keys().collect();
+                names.sort();
+                let lines: Vec<String> = names.iter().map(|name| format!("{} : {}", name, variables[*name].type_name())).collect();
+                return Ok(Output::Text(lines.join("\n")));
+            }
+            "save" if !argument.is_empty() => {
+                fs::write(argument, self.serialize_variables()).map_err(|error| format!("保存失败: {}", error))?;
+                return Ok(Output::Text(format!("已保存到 {}", argument)));
+            }
+            "load" if !argument.is_empty() => {
+                let text = fs::read_to_string(argument).map_err(|error| format!("读取失败: {}", error))?;
+                for (index, entry) in text.lines().enumerate() {
+                    if entry.trim().is_empty() {
+                        continue;
+                    }
+                    self.load_line(entry).map_err(|error| format!("{} 第 {} 行: {}", argument, index + 1, error))?;
+                }
+                return Ok(Output::Text(format!("已从 {} 读取", argument)));
+            }
+            _ => {}
+        }
+
+        let statements = parse_program(line).map_err(|error| error.render(line))?;
+        let mut output = Output::Nothing;
+        for statement in &statements {
+            let value = self.interpreter.execute(statement).map_err(|error| error.render(line))?;
+            output = match statement {
+                Statement::Assign { name, .. } => Output::Assigned(name.clone(), value),
+                Statement::Expression(_) => Output::Value(value),
+            };
+        }
+        return Ok(output);
+    }
+
+    // load 的一行只能是赋值语句，不执行 load、save 等命令，文件读取自身时也不会无限递归
+    fn load_line(&mut self, entry: &str) -> Result<(), String> {
+        if let Some((name, literal)) = quoted_assignment(entry) {
+            let polynomial = literal.parse::<Polynomial>().map_err(|error| error.to_string())?;
+            self.interpreter.set_variable(name, Value::Polynomial(polynomial));
+            return Ok(());
+        }
+        let command = entry.split_whitespace().next().unwrap_or("");
+        if command.starts_with('!') || ["load", "save", "history", "vars", "help", "exit", "quit"].contains(&command) {
+            return Err("只能包含赋值语句".to_string());
+        }
+        let statements = parse_program(entry).map_err(|error| error.render(entry))?;
+        if !statements.iter().all(|statement| matches!(statement, Statement::Assign { .. })) {
+            return Err("只能包含赋值语句".to_string());
+        }
+        for statement in &statements {
+            self.interpreter.execute(statement).map_err(|error| error.render(entry))?;
+        }
+        return Ok(());
+    }
+
+    // 每行一条赋值语句，load 时逐行执行即可还原。
+    // 多项式写成带引号的文本，读取时直接解析，不依赖当时 x 绑定的值
+    fn serialize_variables(&self) -> String {
+        let variables = self.interpreter.get_variables();
+        let mut names: Vec<&String> = variables.keys().collect();
+        names.sort();
+        let mut text = String::new();
+        for name in names {
+            let literal = match &variables[name] {
+                Value::Scalar(value) => format!("{:e}", value),
+                Value::Complex(value) => format!("{} + {}i", value.real, value.imaginary),
+                Value::Matrix(matrix) => matrix_literal(matrix),
+                Value::Polynomial(polynomial) => format!("\"{}\"", polynomial_literal(polynomial)),
+                Value::Roots(_) => continue,
+            };
+            text.push_str(&format!("{} = {}\n", name, literal));
+        }
+        return text;
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session::new()
+    }
+}
+
+pub fn run() {
+    let mut session = Session::new();
+    let stdin = io::stdin();
+    println!("矩阵计算器，输入 help 查看帮助");
+    loop {
+        print!(">> ");
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        match session.execute(&line) {
+            Ok(Output::Exit) => break,
+            Ok(Output::Value(value)) => print_value("ans", &value),
+            Ok(Output::Assigned(name, value)) => print_value(&name, &value),
+            Ok(Output::Text(text)) => println!("{}", text),
+            Ok(Output::Nothing) => {}
+            Err(message) => println!("错误: {}", message),
+        }
+    }
+}
+
+fn print_value(name: &str, value: &Value) {
+    match value {
+        Value::Scalar(number) => println!("{} = {}", name, number),
+        Value::Complex(number) => println!("{} = {}", name, number),
+        Value::Matrix(matrix) => {
+            println!("{} =", name);
+            Matrix::print_matrix(matrix);
+        }
+        Value::Polynomial(polynomial) => println!("{} = {}", name, polynomial),
+        Value::Roots(roots) => {
+            println!("{} =", name);
+            for (index, root) in roots.iter().enumerate() {
+                println!("  x_{} = {}", index + 1, root);
+            }
+        }
+    }
+}
+
+fn matrix_literal(matrix: &Matrix<f64>) -> String {
+    let rows: Vec<String> = (0..matrix.get_rows())
+        .map(|row| (0..matrix.get_cols()).map(|col| format!("{:e}", matrix.get(row, col))).collect::<Vec<String>>().join(" "))
+        .collect();
+    return format!("[{}]", rows.join("; "));
+}
+
+// 能被 Polynomial::from_str 原样读回的文本，系数不做舍入也不用科学计数法
+fn polynomial_literal(polynomial: &Polynomial) -> String {
+    let terms: Vec<String> = polynomial.get_coefficients().iter().enumerate()
+        .map(|(degree, coefficient)| {
+            let sign = if coefficient.imaginary.is_negative() { "-" } else { "+" };
+            format!("({} {} {}i)x^{}", coefficient.real.to_plain_string(), sign, coefficient.imaginary.abs().to_plain_string(), degree)
+        })
+        .collect();
+    return terms.join(" + ");
+}
+
+// 形如 p = "x^2 - 1" 的一行，返回变量名和引号里的多项式
+fn quoted_assignment(entry: &str) -> Option<(&str, &str)> {
+    let (name, value) = entry.split_once('=')?;
+    let literal = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    return Some((name.trim(), literal));
+}
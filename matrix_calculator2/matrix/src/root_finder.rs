@@ -0,0 +1,237 @@
+use bigdecimal::BigDecimal;
+use num_traits::ToPrimitive;
+use crate::complex::Complex;
+use crate::equation::{SolveIteration, SolveOptions, SolveReport};
+use crate::polynomial::Polynomial;
+
+// Jenkins–Traub 第一阶段无位移迭代的次数
+const NO_SHIFT_STEPS: usize = 5;
+// 第三阶段变位移迭代的步数上限，超过之后换一个位移重新开始第二阶段
+const VARIABLE_SHIFT_STEPS: usize = 20;
+// 换位移的次数上限，每次把位移的辐角转 94°
+const MAX_SHIFTS: usize = 20;
+// 收敛之后在原多项式上做 Newton 修正的步数上限，消除降阶带来的误差
+const POLISH_STEPS: usize = 5;
+
+// 多项式系数模的 Cauchy 上界：|a_n| x^n = |a_{n-1}| x^{n-1} + ... + |a_0| 的唯一正根，所有根的模都不超过它
+pub fn cauchy_upper_bound(polynomial: &Polynomial) -> f64 {
+    let magnitudes: Vec<f64> = polynomial.get_coefficients().iter().map(|coefficient| coefficient.abs().to_f64().unwrap_or(f64::MAX)).collect();
+    return positive_root(&magnitudes);
+}
+
+// Cauchy 下界：|a_n| x^n + ... + |a_1| x = |a_0| 的唯一正根，所有根的模都不小于它，有零根时为 0
+pub fn cauchy_lower_bound(polynomial: &Polynomial) -> f64 {
+    let mut magnitudes: Vec<f64> = polynomial.get_coefficients().iter().map(|coefficient| coefficient.abs().to_f64().unwrap_or(f64::MAX)).collect();
+    if magnitudes[0] == 0.0 {
+        return 0.0;
+    }
+    // 倒序之后的多项式的根是原来的根的倒数
+    magnitudes.reverse();
+    return 1.0 / positive_root(&magnitudes);
+}
+
+// a_n - Σ a_i x^{i-n} 关于 x 单调递增，先倍增找到区间再对数二分
+fn positive_root(magnitudes: &[f64]) -> f64 {
+    let degree = magnitudes.len() - 1;
+    if degree == 0 {
+        return 0.0;
+    }
+    let g = |x: f64| magnitudes[degree] - (0..degree).map(|i| magnitudes[i] / x.powi((degree - i) as i32)).sum::<f64>();
+    let (mut low, mut high) = (1.0, 1.0);
+    while g(high) < 0.0 && high < f64::MAX / 2.0 {
+        high *= 2.0;
+    }
+    while g(low) >= 0.0 && low > f64::MIN_POSITIVE * 2.0 {
+        low /= 2.0;
+    }
+    for _ in 0..100 {
+        let middle = (low * high).sqrt();
+        if g(middle) < 0.0 {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    return high;
+}
+
+fn from_polar(radius: f64, angle: f64) -> Complex {
+    return Complex::init(radius * angle.cos(), radius * angle.sin());
+}
+
+// Aberth–Ehrlich 迭代，三阶收敛。初值均匀分布在半径为 Cauchy 上下界几何平均的圆上，
+// 每个根的修正量为 N / (1 - N Σ 1/(z_i - z_j))，N = p(z_i) / p'(z_i)，更新后的根立即用于后面的根
+pub(crate) fn aberth_ehrlich(polynomial: &Polynomial, options: &SolveOptions, mut callback: Option<&mut dyn FnMut(&SolveIteration)>) -> (Vec<Complex>, SolveReport) {
+    let degree = polynomial.degree();
+    let derivative = polynomial.derivative(1);
+    let (lower, upper) = (cauchy_lower_bound(polynomial), cauchy_upper_bound(polynomial));
+    let radius = if lower > 0.0 { (lower * upper).sqrt() } else { upper / 2.0 };
+    let mut roots: Vec<Complex> = (0..degree)
+        .map(|index| from_polar(radius, 2.0 * std::f64::consts::PI * index as f64 / degree as f64 + 0.4).round(options.precision))
+        .collect();
+    let one = Complex::init(1.0, 0.0);
+    let mut report = SolveReport { iterations: 0, residuals: Vec::new(), last_step: None, converged: false };
+    loop {
+        let values: Vec<Complex> = roots.iter().map(|root| polynomial.evaluate(root).round(options.precision)).collect();
+        report.residuals = values.iter().map(|value| value.abs()).collect();
+        if let Some(callback) = callback.as_mut() {
+            callback(&SolveIteration { iteration: report.iterations, roots: &roots, residuals: &report.residuals });
+        }
+        if report.within_tolerance(options) {
+            report.converged = true;
+            break;
+        }
+        if report.iterations >= options.max_iterations {
+            break;
+        }
+        let mut last_step = BigDecimal::from(0);
+        for index in 0..degree {
+            let root = roots[index].clone();
+            let slope = derivative.evaluate(&root).round(options.precision);
+            let step = if slope.is_zero() {
+                // 落在 p' 的零点上时按下标错开一点
+                Complex::init(0.0, -0.001 * (index + 1) as f64)
+            } else {
+                let newton = (&values[index] / &slope).round(options.precision);
+                let mut sum = Complex::new();
+                for (other_index, other) in roots.iter().enumerate() {
+                    let difference = &root - other;
+                    if other_index != index && !difference.is_zero() {
+                        sum = (&sum + &(&one / &difference)).round(options.precision);
+                    }
+                }
+                let denominator = &one - &(&newton * &sum);
+                if denominator.is_zero() { newton } else { (&newton / &denominator).round(options.precision) }
+            };
+            last_step = last_step.max(step.abs());
+            roots[index] = (&root - &step).round(options.precision);
+        }
+        report.last_step = Some(last_step);
+        report.iterations += 1;
+    }
+    return (roots, report);
+}
+
+// Jenkins–Traub 复系数算法（CPOLY）。每次用三个阶段求出一个根：
+// 无位移的 H 多项式迭代、固定位移迭代直到 s - p(s)/H(s) 稳定、变位移迭代直到收敛，
+// 然后把原多项式除以 x - root 降阶，再在原多项式上用 Newton 法修正
+pub(crate) fn jenkins_traub(polynomial: &Polynomial, options: &SolveOptions, mut callback: Option<&mut dyn FnMut(&SolveIteration)>) -> (Vec<Complex>, SolveReport) {
+    let derivative = polynomial.derivative(1);
+    let mut remaining = polynomial.clone();
+    let mut roots: Vec<Complex> = Vec::new();
+    let mut report = SolveReport { iterations: 0, residuals: Vec::new(), last_step: None, converged: true };
+    while remaining.degree() >= 1 {
+        let coefficients = remaining.get_coefficients();
+        let (root, found) = if coefficients[0].is_zero() {
+            (Complex::new(), true)
+        } else if remaining.degree() == 1 {
+            (&Complex::new() - &(&coefficients[0] / &coefficients[1]), true)
+        } else {
+            find_root(&remaining, options, &mut report.iterations)
+        };
+        report.converged &= found;
+        let root = polish(polynomial, &derivative, root, options);
+        remaining = remaining.deflate(&root).round_coefficients(options.precision);
+        roots.push(root);
+        report.residuals = roots.iter().map(|root| polynomial.evaluate(root).abs()).collect();
+        if let Some(callback) = callback.as_mut() {
+            callback(&SolveIteration { iteration: report.iterations, roots: &roots, residuals: &report.residuals });
+        }
+    }
+    return (roots, report);
+}
+
+// 返回求得的根以及是否收敛，不收敛时返回最后的近似值
+fn find_root(polynomial: &Polynomial, options: &SolveOptions, iterations: &mut usize) -> (Complex, bool) {
+    let precision = options.precision;
+    let mut used = 0;
+    // 第一阶段：H_0 = p' / n，位移为 0
+    let mut h = polynomial.derivative(1).monic();
+    let zero = Complex::new();
+    for _ in 0..NO_SHIFT_STEPS {
+        h = next_h(&h, polynomial, &zero, &polynomial.evaluate(&zero), precision);
+        used += 1;
+    }
+    let beta = cauchy_lower_bound(polynomial);
+    let mut angle = 49.0_f64.to_radians();
+    let mut best = from_polar(beta, angle);
+    for attempt in 1..=MAX_SHIFTS {
+        // 第二阶段：固定位移 s，直到 t = s - p(s)/H(s) 连续两次变化不超过一半
+        let shift = from_polar(beta, angle).round(precision);
+        let value = polynomial.evaluate(&shift).round(precision);
+        if value.abs() <= options.residual_tolerance {
+            *iterations += used;
+            return (shift, true);
+        }
+        let mut confirmed = 0;
+        let mut t: Option<Complex> = None;
+        for _ in 0..10 * attempt {
+            h = next_h(&h, polynomial, &shift, &value, precision);
+            used += 1;
+            let h_value = h.evaluate(&shift);
+            if h_value.is_zero() {
+                break;
+            }
+            let next = (&shift - &(&value / &h_value)).round(precision);
+            if let Some(t) = &t {
+                confirmed = if (&next - t).abs() * 2 <= t.abs() { confirmed + 1 } else { 0 };
+            }
+            t = Some(next);
+            if confirmed >= 2 {
+                break;
+            }
+        }
+        // 第三阶段：变位移 s = s - p(s)/H(s)，二次收敛
+        let mut shift = t.unwrap_or(shift);
+        for _ in 0..VARIABLE_SHIFT_STEPS {
+            let value = polynomial.evaluate(&shift).round(precision);
+            if value.abs() <= options.residual_tolerance {
+                *iterations += used;
+                return (shift, true);
+            }
+            h = next_h(&h, polynomial, &shift, &value, precision);
+            used += 1;
+            let h_value = h.evaluate(&shift);
+            if h_value.is_zero() {
+                break;
+            }
+            let step = (&value / &h_value).round(precision);
+            shift = (&shift - &step).round(precision);
+            if step.abs() <= options.step_tolerance {
+                *iterations += used;
+                return (shift, true);
+            }
+        }
+        best = shift;
+        if used >= options.max_iterations {
+            break;
+        }
+        angle += 94.0_f64.to_radians();
+    }
+    *iterations += used;
+    return (best, false);
+}
+
+// H_{k+1}(x) = (H_k(x) - H_k(s)/p(s) p(x)) / (x - s)，再化为首一，这样 H(s) 就是归一化之后的值
+fn next_h(h: &Polynomial, polynomial: &Polynomial, shift: &Complex, value: &Complex, precision: i64) -> Polynomial {
+    if value.is_zero() {
+        return h.clone();
+    }
+    let ratio = &h.evaluate(shift) / value;
+    return (h - &(polynomial * &ratio)).deflate(shift).monic().round_coefficients(precision);
+}
+
+pub(crate) fn polish(polynomial: &Polynomial, derivative: &Polynomial, mut root: Complex, options: &SolveOptions) -> Complex {
+    for _ in 0..POLISH_STEPS {
+        let slope = derivative.evaluate(&root);
+        if slope.is_zero() {
+            break;
+        }
+        let step = (&polynomial.evaluate(&root) / &slope).round(options.precision);
+        root = (&root - &step).round(options.precision);
+        if step.abs() <= options.step_tolerance {
+            break;
+        }
+    }
+    return root;
+}
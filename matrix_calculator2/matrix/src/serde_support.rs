@@ -0,0 +1,56 @@
+use std::ops::{Add, Div, Mul, Sub};
+use num_traits::One;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::matrix::Matrix;
+
+#[derive(Serialize, Deserialize)]
+struct MatrixRepr<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Serialize for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + Serialize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut data: Vec<T> = Vec::with_capacity(self.get_rows() * self.get_cols());
+        for row in 0..self.get_rows() {
+            for col in 0..self.get_cols() {
+                data.push(*self.get(row, col));
+            }
+        }
+        let repr = MatrixRepr { rows: self.get_rows(), cols: self.get_cols(), data };
+        return repr.serialize(serializer);
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + std::fmt::Display + Deserialize<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr: MatrixRepr<T> = MatrixRepr::deserialize(deserializer)?;
+        // 行列数来自外部数据，乘积溢出时同样按大小不符处理
+        if repr.rows.checked_mul(repr.cols) != Some(repr.data.len()) {
+            return Err(D::Error::custom(format!("矩阵大小为 {}x{}，但有 {} 个元素", repr.rows, repr.cols, repr.data.len())));
+        }
+        return Ok(Matrix::init(repr.rows, repr.cols, repr.data));
+    }
+}
+
+// BigDecimal 以字符串形式保存，避免精度损失
+#[cfg(feature = "bigdecimal")]
+pub mod big_decimal_string {
+    use std::str::FromStr;
+    use bigdecimal::BigDecimal;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &BigDecimal, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(&value.to_string());
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigDecimal, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        return BigDecimal::from_str(&text).map_err(|error| D::Error::custom(format!("无法解析 \"{}\": {}", text, error)));
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+use num_traits::One;
+use crate::matrix::Matrix;
+
+// 编译期确定大小、分配在栈上的矩阵，适合 2x2 到 4x4 的几何变换。
+// 维度是类型的一部分，矩阵乘法只有在内侧维度相同时才能通过编译
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SMatrix<T, const R: usize, const C: usize> {
+    data: [[T; C]; R],
+}
+
+// 从动态矩阵转换时大小不符
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SizeMismatch {
+    pub expected: (usize, usize),
+    pub found: (usize, usize),
+}
+
+impl fmt::Display for SizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "矩阵大小应为 {}x{}，实际为 {}x{}", self.expected.0, self.expected.1, self.found.0, self.found.1)
+    }
+}
+
+impl<T, const R: usize, const C: usize> SMatrix<T, R, C> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn new() -> Self {
+        SMatrix { data: [[T::default(); C]; R] }
+    }
+
+    pub fn from_rows(data: [[T; C]; R]) -> Self {
+        SMatrix { data }
+    }
+
+    pub fn get_rows(&self) -> usize {
+        return R;
+    }
+
+    pub fn get_cols(&self) -> usize {
+        return C;
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> &T {
+        return &self.data[row][col];
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        self.data[row][col] = value;
+    }
+
+    pub fn transpose(&self) -> SMatrix<T, C, R> {
+        let mut result: SMatrix<T, C, R> = SMatrix::new();
+        for row in 0..R {
+            for col in 0..C {
+                result.data[col][row] = self.data[row][col];
+            }
+        }
+        return result;
+    }
+}
+
+impl<T, const N: usize> SMatrix<T, N, N> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn identity() -> Self {
+        let mut result: SMatrix<T, N, N> = SMatrix::new();
+        for index in 0..N {
+            result.data[index][index] = T::one();
+        }
+        return result;
+    }
+}
+
+impl<T> SMatrix<T, 2, 2> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn determinant(&self) -> T {
+        let [[a, b], [c, d]] = self.data;
+        return a * d - b * c;
+    }
+
+    // 行列式为零时返回 None
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == T::default() {
+            return None;
+        }
+        let [[a, b], [c, d]] = self.data;
+        let zero = T::default();
+        return Some(SMatrix::from_rows([
+            [d / determinant, (zero - b) / determinant],
+            [(zero - c) / determinant, a / determinant],
+        ]));
+    }
+}
+
+impl<T> SMatrix<T, 3, 3> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    pub fn determinant(&self) -> T {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.data;
+        return a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g);
+    }
+
+    // 伴随矩阵除以行列式，行列式为零时返回 None
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == T::default() {
+            return None;
+        }
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.data;
+        let adjugate = [
+            [e * i - f * h, c * h - b * i, b * f - c * e],
+            [f * g - d * i, a * i - c * g, c * d - a * f],
+            [d * h - e * g, b * g - a * h, a * e - b * d],
+        ];
+        return Some(SMatrix::from_rows(adjugate.map(|row| row.map(|value| value / determinant))));
+    }
+}
+
+impl<T, const R: usize, const C: usize> Default for SMatrix<T, R, C> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    fn default() -> Self {
+        SMatrix::new()
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for SMatrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        return &self.data[row][col];
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for SMatrix<T, R, C> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        return &mut self.data[row][col];
+    }
+}
+
+impl<T, const R: usize, const C: usize> Add for SMatrix<T, R, C> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    type Output = SMatrix<T, R, C>;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        for row in 0..R {
+            for col in 0..C {
+                self.data[row][col] = self.data[row][col] + other.data[row][col];
+            }
+        }
+        return self;
+    }
+}
+
+impl<T, const R: usize, const C: usize> Sub for SMatrix<T, R, C> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    type Output = SMatrix<T, R, C>;
+
+    fn sub(mut self, other: Self) -> Self::Output {
+        for row in 0..R {
+            for col in 0..C {
+                self.data[row][col] = self.data[row][col] - other.data[row][col];
+            }
+        }
+        return self;
+    }
+}
+
+impl<T, const R: usize, const K: usize, const C: usize> Mul<SMatrix<T, K, C>> for SMatrix<T, R, K> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    type Output = SMatrix<T, R, C>;
+
+    fn mul(self, other: SMatrix<T, K, C>) -> Self::Output {
+        let mut result: SMatrix<T, R, C> = SMatrix::new();
+        for row in 0..R {
+            for col in 0..C {
+                let mut sum = T::default();
+                for k in 0..K {
+                    sum = sum + self.data[row][k] * other.data[k][col];
+                }
+                result.data[row][col] = sum;
+            }
+        }
+        return result;
+    }
+}
+
+impl<T, const R: usize, const C: usize> Mul<T> for SMatrix<T, R, C> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    type Output = SMatrix<T, R, C>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        return SMatrix::from_rows(self.data.map(|row| row.map(|value| value * rhs)));
+    }
+}
+
+impl<T, const R: usize, const C: usize> From<SMatrix<T, R, C>> for Matrix<T> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    fn from(matrix: SMatrix<T, R, C>) -> Self {
+        return Matrix::init(R, C, matrix.data.iter().flatten().copied().collect());
+    }
+}
+
+impl<T, const R: usize, const C: usize> TryFrom<&Matrix<T>> for SMatrix<T, R, C> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    type Error = SizeMismatch;
+
+    fn try_from(matrix: &Matrix<T>) -> Result<Self, Self::Error> {
+        if matrix.get_rows() != R || matrix.get_cols() != C {
+            return Err(SizeMismatch { expected: (R, C), found: (matrix.get_rows(), matrix.get_cols()) });
+        }
+        let mut result: SMatrix<T, R, C> = SMatrix::new();
+        for row in 0..R {
+            for col in 0..C {
+                result.data[row][col] = *matrix.get(row, col);
+            }
+        }
+        return Ok(result);
+    }
+}
+
+impl<T, const R: usize, const C: usize> fmt::Display for SMatrix<T, R, C> where
+        T: Copy + Default + PartialOrd + One + Add<Output = T> + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let matrix: Matrix<T> = Matrix::from(*self);
+        return match f.precision() {
+            Some(precision) => write!(f, "{:.*}", precision, matrix),
+            None => write!(f, "{}", matrix),
+        };
+    }
+}
@@ -1,13 +1,45 @@
-use bigdecimal::{BigDecimal, FromPrimitive};
-use std::str::FromStr;
-use std::ops::{Add, Sub, Mul, Div};
-
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "bigdecimal")]
+    use bigdecimal::{BigDecimal, FromPrimitive};
+    use std::str::FromStr;
+    #[cfg(feature = "bigdecimal")]
+    use crate::cli;
+    #[cfg(feature = "bigdecimal")]
     use crate::complex::Complex;
-    use crate::equation::EquationWithDegrees;
-    use super::*;
+    use crate::elementary::{ElementaryOperation, ElementaryRecorder};
+    use crate::elimination_trace::TraceStep;
+    #[cfg(feature = "bigdecimal")]
+    use crate::equation::{EquationWithDegrees, RootFinder, SolveIteration, SolveOptions};
+    use crate::matrix::Matrix;
+    use crate::matrix_format::{Alignment, MatrixFormatter, OutputStyle};
+    use crate::matrix_io::{CsvOptions, MarketFormat};
+    use crate::matrix_parser::ParseMatrixError;
+    use crate::permutation::Permutation;
+    #[cfg(feature = "bigdecimal")]
+    use crate::polynomial::Polynomial;
+    #[cfg(feature = "bigdecimal")]
+    use crate::polynomial_parser::{ParsePolynomialError, MAX_DEGREE};
+    #[cfg(feature = "bigdecimal")]
+    use crate::root_finder::{cauchy_lower_bound, cauchy_upper_bound};
+    use crate::smatrix::{SMatrix, SizeMismatch};
+    #[cfg(feature = "bigdecimal")]
+    use crate::expression::{ErrorKind, Interpreter, Span};
+    #[cfg(feature = "bigdecimal")]
+    use crate::repl::{Output, Session, Value};
+
+    fn assert_matrix_close(actual: &Matrix<f64>, expected: &Matrix<f64>, tolerance: f64) {
+        assert_eq!(actual.get_rows(), expected.get_rows());
+        assert_eq!(actual.get_cols(), expected.get_cols());
+        for row in 0..actual.get_rows() {
+            for col in 0..actual.get_cols() {
+                let (a, e) = (*actual.get(row, col), *expected.get(row, col));
+                assert!((a - e).abs() <= tolerance, "({}, {}): {} != {}", row, col, a, e);
+            }
+        }
+    }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_new() {
         let complex = Complex::new();
@@ -15,6 +47,7 @@ mod tests {
         assert_eq!(complex.imaginary, BigDecimal::from_f64(0.0).unwrap());
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_init() {
         let complex = Complex::init(3.0, 4.0);
@@ -22,6 +55,7 @@ mod tests {
         assert_eq!(complex.imaginary, BigDecimal::from_f64(4.0).unwrap());
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_conjugate() {
         let complex = Complex::init(3.0, 4.0);
@@ -30,6 +64,7 @@ mod tests {
         assert_eq!(conjugate.imaginary, BigDecimal::from_f64(-4.0).unwrap());
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_add() {
         let complex1 = Complex::init(3.0, 4.0);
@@ -39,6 +74,7 @@ mod tests {
         assert_eq!(result.imaginary, BigDecimal::from_f64(6.0).unwrap());
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_sub() {
         let complex1 = Complex::init(5.0, 7.0);
@@ -48,6 +84,7 @@ mod tests {
         assert_eq!(result.imaginary, BigDecimal::from_f64(5.0).unwrap());
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_mul() {
         let complex1 = Complex::init(1.0, 2.0);
@@ -58,6 +95,7 @@ mod tests {
         assert_eq!(result.imaginary, BigDecimal::from_f64(10.0).unwrap());
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_div() {
         let complex1 = Complex::init(3.0, 2.0);
@@ -68,6 +106,7 @@ mod tests {
         assert!(result.imaginary - BigDecimal::from_f64(17.0/25.0).unwrap() < BigDecimal::from_f64(0.0001).unwrap());
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_pow() {
         let complex = Complex::init(2.0, 3.0);
@@ -76,9 +115,15 @@ mod tests {
         print!("{}",result);
         assert!((BigDecimal::abs(&(result.real - BigDecimal::from_f64(122.0).unwrap()))) < BigDecimal::from_f64(0.0001).unwrap());
         assert!((BigDecimal::abs(&(result.imaginary - BigDecimal::from_f64(-597.0).unwrap()))) < BigDecimal::from_f64(0.0001).unwrap());
+        // 大指数用反复平方，(1 + i)^20000 = (2i)^10000 = 2^10000
+        let large = Complex::complex_pow(&Complex::init(1.0, 1.0), 20000);
+        let expected = BigDecimal::from(2).powi(10000);
+        assert!(((&large.real - &expected) / &expected).abs() < BigDecimal::from_str("1e-50").unwrap());
+        assert!((&large.imaginary / &expected).abs() < BigDecimal::from_str("1e-50").unwrap());
 
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_to_polar_and_from_polar() {
         let complex = Complex::init(1.0, 1.0);
@@ -92,8 +137,15 @@ mod tests {
         let complex_back = Complex::from_polar(&r, &theta);
         assert!(complex_back.real - BigDecimal::from_f64(1.0).unwrap() < BigDecimal::from_f64(0.0001).unwrap());
         assert!(complex_back.imaginary - BigDecimal::from_f64(1.0).unwrap() < BigDecimal::from_f64(0.0001).unwrap());
+
+        // 级数项数由参数决定：只取一项时 sin(x) ≈ x
+        let angle = BigDecimal::from_f64(0.5).unwrap();
+        assert_eq!(Complex::calc_sin(&angle, 1), angle);
+        let sine = bigdecimal::ToPrimitive::to_f64(&Complex::calc_sin(&angle, 30)).unwrap();
+        assert!((sine - 0.5f64.sin()).abs() < 1e-15);
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_quadratic_polynomial() {
         // 创建一个表示 x^2 - 1 = 0 的多项式
@@ -103,8 +155,8 @@ mod tests {
             Complex::from_big_decimal(&BigDecimal::from(1), &BigDecimal::from(0)),  // x^2 的系数 1
         ];
         let equation = EquationWithDegrees::new(parameters);
-        // 设置一个合理的误差阈值
-        let threshold = BigDecimal::from_str("0.001").unwrap();
+        // 二次方程直接用求根公式，结果是精确的
+        let threshold = BigDecimal::from_str("0.000000000000000000001").unwrap();
         let roots = equation.solve(&threshold);
 
         // 期望的根是 1 和 -1
@@ -115,6 +167,1190 @@ mod tests {
         assert!(roots.iter().any(|root| (root - &expected_root1).abs() < threshold));
         assert!(roots.iter().any(|root| (root - &expected_root2).abs() < threshold));
     }
+
+    #[cfg(feature = "bigdecimal")]
+    fn assert_polynomial_close(actual: &Polynomial, expected: &[f64]) {
+        assert_eq!(actual.degree() + 1, expected.len(), "系数个数不同");
+        for (coefficient, value) in actual.get_coefficients().iter().zip(expected) {
+            assert!((coefficient - &Complex::init(*value, 0.0)).abs() < BigDecimal::from_f64(1e-9).unwrap(),
+                "{} != {}", coefficient, value);
+        }
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_polynomial() {
+        // x^2 - 1 与 x + 1
+        let p = Polynomial::from_f64(&[-1.0, 0.0, 1.0]);
+        let q = Polynomial::from_f64(&[1.0, 1.0]);
+        assert_eq!(p.degree(), 2);
+        assert_polynomial_close(&Polynomial::constant(p.leading_coefficient()), &[1.0]);
+
+        // 最高次的零系数会被去掉
+        let padded = Polynomial::from_f64(&[1.0, 2.0, 0.0, 0.0]);
+        assert_eq!(padded.degree(), 1);
+        assert!(Polynomial::from_f64(&[0.0, 0.0]).is_zero());
+        assert!((&p - &p).is_zero());
+
+        assert_polynomial_close(&(&p + &q), &[0.0, 1.0, 1.0]);
+        assert_polynomial_close(&(&p - &q), &[-2.0, -1.0, 1.0]);
+        assert_polynomial_close(&(&p * &q), &[-1.0, -1.0, 1.0, 1.0]);
+        assert_polynomial_close(&-&q, &[-1.0, -1.0]);
+        assert_polynomial_close(&(&q * &Complex::init(2.0, 0.0)), &[2.0, 2.0]);
+        assert_polynomial_close(&(&q / &Complex::init(2.0, 0.0)), &[0.5, 0.5]);
+
+        // (2x^3 + 3x + 5) = (x^2 + 1)(2x) + (x + 5)
+        let dividend = Polynomial::from_f64(&[5.0, 3.0, 0.0, 2.0]);
+        let divisor = Polynomial::from_f64(&[1.0, 0.0, 1.0]);
+        let (quotient, remainder) = dividend.div_rem(&divisor);
+        assert_polynomial_close(&quotient, &[0.0, 2.0]);
+        assert_polynomial_close(&remainder, &[5.0, 1.0]);
+        assert_polynomial_close(&(&dividend / &divisor), &[0.0, 2.0]);
+        assert_polynomial_close(&(&dividend % &divisor), &[5.0, 1.0]);
+        assert_polynomial_close(&(&(&quotient * &divisor) + &remainder), &[5.0, 3.0, 0.0, 2.0]);
+        // 除数次数更高时商为零
+        let (quotient, remainder) = q.div_rem(&p);
+        assert!(quotient.is_zero());
+        assert_polynomial_close(&remainder, &[1.0, 1.0]);
+        // 除以常数多项式没有余数
+        let (quotient, remainder) = p.div_rem(&Polynomial::from_f64(&[2.0]));
+        assert_polynomial_close(&quotient, &[-0.5, 0.0, 0.5]);
+        assert!(remainder.is_zero());
+
+        // p(q(x)) = (x + 1)^2 - 1 = x^2 + 2x
+        assert_polynomial_close(&p.compose(&q), &[0.0, 2.0, 1.0]);
+        assert_polynomial_close(&p.monic(), &[-1.0, 0.0, 1.0]);
+
+        // p(1 + i) = (1 + i)^2 - 1 = -1 + 2i
+        let value = p.evaluate(&Complex::init(1.0, 1.0));
+        assert!((&value - &Complex::init(-1.0, 2.0)).abs() < BigDecimal::from_f64(1e-12).unwrap());
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_polynomial_gcd_and_multiplicities() {
+        let tolerance = BigDecimal::from_str("0.0000001").unwrap();
+        // gcd(x^2 - 1, x^2 + 2x + 1) = x + 1
+        let gcd = Polynomial::from_f64(&[-1.0, 0.0, 1.0]).gcd(&Polynomial::from_f64(&[1.0, 2.0, 1.0]), &tolerance);
+        assert_polynomial_close(&gcd, &[1.0, 1.0]);
+        // 互素时为 1
+        let gcd = Polynomial::from_f64(&[-1.0, 1.0]).gcd(&Polynomial::from_f64(&[2.0, 1.0]), &tolerance);
+        assert_polynomial_close(&gcd, &[1.0]);
+
+        // 2 (x - 1)^3 (x + 2) = 2x^4 - 2x^3 - 6x^2 + 10x - 4
+        let polynomial = Polynomial::from_f64(&[-4.0, 10.0, -6.0, -2.0, 2.0]);
+        let factors = polynomial.square_free_decomposition(&tolerance);
+        assert_eq!(factors.len(), 2);
+        assert_polynomial_close(&factors[0].0, &[2.0, 1.0]);
+        assert_eq!(factors[0].1, 1);
+        assert_polynomial_close(&factors[1].0, &[-1.0, 1.0]);
+        assert_eq!(factors[1].1, 3);
+
+        // (x - 1)^3 只有一个三重根
+        let cube = Polynomial::from_f64(&[-1.0, 3.0, -3.0, 1.0]);
+        let roots = cube.solve_with_multiplicities(&tolerance);
+        assert_eq!(roots.len(), 1);
+        assert!((&roots[0].0 - &Complex::init(1.0, 0.0)).abs() < tolerance);
+        assert_eq!(roots[0].1, 3);
+
+        // (x^2 + 1)^2 (x - 3)：±i 各为二重根
+        let polynomial = &(&Polynomial::from_f64(&[1.0, 0.0, 1.0]) * &Polynomial::from_f64(&[1.0, 0.0, 1.0]))
+            * &Polynomial::from_f64(&[-3.0, 1.0]);
+        let roots = polynomial.solve_with_multiplicities(&tolerance);
+        assert_eq!(roots.len(), 3);
+        let find = |expected: Complex| roots.iter().find(|(root, _)| (root - &expected).abs() < tolerance).map(|(_, multiplicity)| *multiplicity);
+        assert_eq!(find(Complex::init(3.0, 0.0)), Some(1));
+        assert_eq!(find(Complex::init(0.0, 1.0)), Some(2));
+        assert_eq!(find(Complex::init(0.0, -1.0)), Some(2));
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_solve_options() {
+        // 2x^2 - 8，残差按首一化之后的 x^2 - 4 计算
+        let polynomial = Polynomial::from_f64(&[-8.0, 0.0, 2.0]);
+        let mut history: Vec<(usize, usize)> = Vec::new();
+        let mut callback = |iteration: &SolveIteration| history.push((iteration.iteration, iteration.roots.len()));
+        let iterative = SolveOptions { closed_form: false, ..SolveOptions::default() };
+        let (roots, report) = polynomial.solve_with_options(&iterative, Some(&mut callback));
+        assert!(report.converged);
+        assert!(report.iterations > 0);
+        assert_eq!(history.len(), report.iterations + 1);
+        assert!(history.iter().enumerate().all(|(index, (iteration, count))| index == *iteration && *count == 2));
+        assert_eq!(report.residuals.len(), 2);
+        assert!(report.residuals.iter().all(|residual| *residual <= SolveOptions::default().residual_tolerance));
+        assert!(roots.iter().any(|root| (root - &Complex::init(2.0, 0.0)).abs() < BigDecimal::from_f64(1e-6).unwrap()));
+        assert!(roots.iter().any(|root| (root - &Complex::init(-2.0, 0.0)).abs() < BigDecimal::from_f64(1e-6).unwrap()));
+
+        // 迭代次数用完时返回当前的近似值并报告不收敛
+        let options = SolveOptions { max_iterations: 2, closed_form: false, ..SolveOptions::default() };
+        let (roots, report) = Polynomial::from_f64(&[-1.0, 3.0, -3.0, 1.0]).solve_with_options(&options, None);
+        assert_eq!(roots.len(), 3);
+        assert_eq!(report.iterations, 2);
+        assert!(!report.converged);
+        assert!(report.last_step.is_some());
+
+        // 步长足够小也算收敛
+        let options = SolveOptions { residual_tolerance: BigDecimal::from(0), step_tolerance: BigDecimal::from_f64(1e-3).unwrap(), closed_form: false, ..SolveOptions::default() };
+        let (_, report) = Polynomial::from_f64(&[-4.0, 0.0, 1.0]).solve_with_options(&options, None);
+        assert!(report.converged);
+        assert!(report.last_step.unwrap() <= BigDecimal::from_f64(1e-3).unwrap());
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_root_finders() {
+        // (x - 1)(x - 2)...(x - 10)
+        let mut wilkinson = Polynomial::from_f64(&[1.0]);
+        for k in 1..=10 {
+            wilkinson = &wilkinson * &Polynomial::from_f64(&[-(k as f64), 1.0]);
+        }
+        let lower = cauchy_lower_bound(&wilkinson);
+        let upper = cauchy_upper_bound(&wilkinson);
+        assert!(lower > 0.0 && lower <= 1.0 && upper >= 10.0);
+        // 3x^3 - 2x^2 + 12x - 8 = (3x - 2)(x^2 + 4)，含一对共轭复根
+        let complex = Polynomial::from_f64(&[-8.0, 12.0, -2.0, 3.0]);
+        let tolerance = BigDecimal::from_f64(1e-6).unwrap();
+        let mut iterations: Vec<usize> = Vec::new();
+        for method in [RootFinder::DurandKerner, RootFinder::AberthEhrlich, RootFinder::JenkinsTraub] {
+            let options = SolveOptions { method, closed_form: false, ..SolveOptions::default() };
+            let (roots, report) = wilkinson.solve_with_options(&options, None);
+            assert!(report.converged, "{:?}", method);
+            iterations.push(report.iterations);
+            assert_eq!(roots.len(), 10);
+            for k in 1..=10 {
+                assert!(roots.iter().any(|root| (root - &Complex::init(k as f64, 0.0)).abs() < tolerance), "{:?} 缺少根 {}", method, k);
+            }
+
+            let (roots, report) = complex.solve_with_options(&options, None);
+            assert!(report.converged, "{:?}", method);
+            for expected in [Complex::init(2.0 / 3.0, 0.0), Complex::init(0.0, 2.0), Complex::init(0.0, -2.0)] {
+                assert!(roots.iter().any(|root| (root - &expected).abs() < tolerance), "{:?} 缺少根 {}", method, expected);
+            }
+        }
+        // Aberth 三阶收敛，迭代次数明显少于 Durand–Kerner
+        assert!(iterations[1] < iterations[0]);
+        // 零根与常数多项式
+        let (roots, report) = Polynomial::from_f64(&[0.0, -1.0, 1.0])
+            .solve_with_options(&SolveOptions { method: RootFinder::JenkinsTraub, closed_form: false, ..SolveOptions::default() }, None);
+        assert!(report.converged && roots.len() == 2);
+        assert!(Polynomial::from_f64(&[5.0]).solve_with_options(&SolveOptions::default(), None).0.is_empty());
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_solve_via_companion() {
+        // 平衡之后非对角元的量级接近，特征值不变
+        let matrix = Matrix::init(2, 2, vec![1.0, 1e6, 1e-6, 2.0]);
+        let balanced = Matrix::balance(&matrix);
+        assert!(balanced.get(0, 1).abs() < 10.0 && balanced.get(1, 0).abs() > 0.1);
+        assert_eq!((balanced.get(0, 0), balanced.get(1, 1)), (&1.0, &2.0));
+        let mut original = Matrix::eigenvalues(&matrix).unwrap();
+        let mut after = Matrix::eigenvalues(&balanced).unwrap();
+        original.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        after.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        for (a, b) in original.iter().zip(&after) {
+            assert!((a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9);
+        }
+
+        // 2x^3 - 4x^2 - 22x + 24 = 2(x - 1)(x + 3)(x - 4)
+        let companion = Polynomial::from_f64(&[24.0, -22.0, -4.0, 2.0]).companion_matrix().unwrap();
+        assert_matrix_close(&companion, &Matrix::init(3, 3, vec![2.0, 11.0, -12.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]), 0.0);
+        assert!(Polynomial::from_f64(&[3.0]).companion_matrix().is_none());
+        assert!("ix + 1".parse::<Polynomial>().unwrap().solve_via_companion().is_none());
+        assert_eq!(Polynomial::from_f64(&[3.0]).solve_via_companion().map(|roots| roots.len()), Some(0));
+
+        // 与 Durand–Kerner 的结果互相验证
+        let threshold = BigDecimal::from_str("0.000000001").unwrap();
+        let tolerance = BigDecimal::from_f64(1e-6).unwrap();
+        let mut wilkinson = Polynomial::from_f64(&[1.0]);
+        for k in 1..=8 {
+            wilkinson = &wilkinson * &Polynomial::from_f64(&[-(k as f64), 1.0]);
+        }
+        let polynomials = [
+            Polynomial::from_f64(&[24.0, -22.0, -4.0, 2.0]),
+            Polynomial::from_f64(&[1.0, 0.0, 0.0, 0.0, 1.0]),
+            Polynomial::from_f64(&[-8.0, 12.0, -2.0, 3.0]),
+            Polynomial::from_f64(&[0.001, -3.0, 0.0, 0.0, 0.0, 1000.0]),
+            wilkinson,
+        ];
+        for polynomial in &polynomials {
+            let companion_roots = polynomial.solve_via_companion().unwrap();
+            let roots = polynomial.solve(&threshold);
+            assert_eq!(companion_roots.len(), roots.len());
+            for root in &roots {
+                assert!(companion_roots.iter().any(|other| (other - root).abs() < tolerance), "{} 的根 {} 不一致", polynomial, root);
+            }
+        }
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_polynomial_parse_and_display() {
+        let cubic: EquationWithDegrees = "2x^3 + 6x^2 - 2x - 1".parse().unwrap();
+        assert_polynomial_close(&cubic, &[-1.0, -2.0, 6.0, 2.0]);
+        assert_eq!(cubic.to_string(), "2x^3 + 6x^2 - 2x - 1");
+        assert_eq!(format!("{:#}", cubic), "2x³ + 6x² - 2x - 1");
+
+        // 省略的系数、上标、乘号与 ** 以及其他字母作为未知数
+        assert_polynomial_close(&"-x² + x".parse().unwrap(), &[0.0, 1.0, -1.0]);
+        assert_polynomial_close(&"3 * t**2 - 0.5t".parse().unwrap(), &[0.0, -0.5, 3.0]);
+        assert_polynomial_close(&"x^2 + x^2 - 4".parse().unwrap(), &[-4.0, 0.0, 2.0]);
+        // 方程整理为左边减右边
+        assert_polynomial_close(&"x^2 = 2x + 1".parse().unwrap(), &[-1.0, -2.0, 1.0]);
+
+        let complex: Polynomial = "(1+2i)x^2 - ix + 3i".parse().unwrap();
+        let coefficients = complex.get_coefficients();
+        assert!((&coefficients[2] - &Complex::init(1.0, 2.0)).abs() < BigDecimal::from_f64(1e-12).unwrap());
+        assert!((&coefficients[1] - &Complex::init(0.0, -1.0)).abs() < BigDecimal::from_f64(1e-12).unwrap());
+        assert!((&coefficients[0] - &Complex::init(0.0, 3.0)).abs() < BigDecimal::from_f64(1e-12).unwrap());
+        assert_eq!(complex.to_string(), "(1 + 2i)x^2 - ix + 3i");
+        // 输出可以读回
+        let restored: Polynomial = complex.to_string().parse().unwrap();
+        assert_eq!(restored.to_string(), complex.to_string());
+
+        assert_eq!(Polynomial::zero().to_string(), "0");
+        assert_eq!(Polynomial::from_f64(&[10.0, 0.0, -1.0]).to_string(), "-x^2 + 10");
+        assert_eq!(format!("{:.2}", Polynomial::from_f64(&[1.0 / 3.0, 1.0])), "x + 0.33");
+
+        assert_eq!("".parse::<Polynomial>().err(), Some(ParsePolynomialError::Empty));
+        assert_eq!("x^2 + y".parse::<Polynomial>().err(), Some(ParsePolynomialError::MixedVariables { expected: 'x', found: 'y' }));
+        assert_eq!("x = 1 = 2".parse::<Polynomial>().err(), Some(ParsePolynomialError::MultipleEquals { position: 6 }));
+        assert_eq!("2x + ".parse::<Polynomial>().err(), Some(ParsePolynomialError::UnexpectedEnd));
+        assert_eq!("2x $ 1".parse::<Polynomial>().err(), Some(ParsePolynomialError::UnexpectedCharacter { position: 3, found: '$' }));
+        assert_eq!("1.2.3x".parse::<Polynomial>().err(),
+            Some(ParsePolynomialError::InvalidNumber { position: 0, token: "1.2.3".to_string() }));
+        assert_eq!("x¹⁸⁴⁴⁶⁷⁴⁴⁰⁷³⁷⁰⁹⁵⁵¹⁶¹⁶".parse::<Polynomial>().err(),
+            Some(ParsePolynomialError::InvalidNumber { position: 1, token: "¹⁸⁴⁴⁶⁷⁴⁴⁰⁷³⁷⁰⁹⁵⁵¹⁶¹⁶".to_string() }));
+        assert_eq!("x^1000000000000".parse::<Polynomial>().err(), Some(ParsePolynomialError::ExponentTooLarge { position: 2, limit: MAX_DEGREE }));
+        assert_eq!("3x¹⁰⁰⁰⁰¹".parse::<Polynomial>().err(), Some(ParsePolynomialError::ExponentTooLarge { position: 2, limit: MAX_DEGREE }));
+        assert_eq!("x^10000".parse::<Polynomial>().unwrap().degree(), 10000);
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_closed_form() {
+        let radicals = |text: &str| text.parse::<Polynomial>().unwrap().solve_radicals().unwrap();
+        assert_eq!(radicals("2x + 3"), vec!["-1.5"]);
+        assert_eq!(radicals("x^2 - 1"), vec!["1", "-1"]);
+        assert_eq!(radicals("x^2 - 2"), vec!["√8/2", "-√8/2"]);
+        assert_eq!(radicals("x^2 + 1"), vec!["i", "-i"]);
+        assert_eq!(radicals("x^3 + x + 1")[0], "-(∛((27 + √837)/2) - 3/∛((27 + √837)/2))/3");
+        assert_eq!(radicals("x^4 - 10x^3 + 35x^2 - 50x + 24"), vec!["4", "1", "3", "2"]);
+        assert!(Polynomial::from_f64(&[1.0, 0.0, 0.0, 0.0, 0.0, 1.0]).solve_closed_form().is_none());
+        assert_eq!(Polynomial::from_f64(&[2.0]).solve_radicals().map(|roots| roots.len()), Some(0));
+        // 超出 f64 范围的立方根不会因初值为 0 而除以零
+        for text in ["1e-400", "-8e-600", "2.7e700"] {
+            let value = BigDecimal::from_str(text).unwrap();
+            let root = Complex::from_big_decimal(&value, &BigDecimal::from(0)).cbrt();
+            let cube = &(&root * &root) * &root;
+            assert!(((&cube.real - &value) / &value).abs() < BigDecimal::from_str("1e-50").unwrap());
+        }
+        let tiny = format!("x^3 - 0.{}1", "0".repeat(399));
+        assert_eq!(tiny.parse::<Polynomial>().unwrap().solve_closed_form().unwrap().len(), 3);
+
+        // 三重根、四重根、Ferrari 的一般情形以及复系数，公式解的残差都很小
+        let tolerance = BigDecimal::from_str("1e-50").unwrap();
+        for text in ["x^3 - 3x^2 + 3x - 1", "x^3 - 6x^2 + 11x - 6", "x^3 - 1", "x^4 - 4x^3 + 6x^2 - 4x + 1", "x^4 - 6x^2 + 8x - 3",
+                     "x^4 + x + 1", "3x^4 - 2x^3 + x - 7", "(1+i)x^2 + 2x - i", "x^3 + (2 - i)x + 1"] {
+            let polynomial: Polynomial = text.parse().unwrap();
+            let roots = polynomial.solve_closed_form().unwrap();
+            assert_eq!(roots.len(), polynomial.degree());
+            for root in &roots {
+                assert!(polynomial.evaluate(root).abs() < tolerance, "{} 在 {} 处的残差过大", text, root);
+            }
+        }
+        let roots = Polynomial::from_f64(&[1.0, 1.0, 0.0, 1.0]).solve_closed_form().unwrap();
+        assert!((&roots[0] - &Complex::init(-0.6823278038280193, 0.0)).abs() < BigDecimal::from_f64(1e-12).unwrap());
+
+        // 四次以下默认直接用公式，不再迭代，回调只调用一次
+        let mut calls = 0;
+        let mut callback = |_: &SolveIteration| calls += 1;
+        let (roots, report) = Polynomial::from_f64(&[-6.0, 11.0, -6.0, 1.0]).solve_with_options(&SolveOptions::default(), Some(&mut callback));
+        assert!(report.converged && report.iterations == 0 && report.last_step.is_none());
+        assert_eq!(calls, 1);
+        for k in 1..=3 {
+            assert!(roots.iter().any(|root| (root - &Complex::init(k as f64, 0.0)).abs() < BigDecimal::from_str("1e-25").unwrap()));
+        }
+        let (_, report) = Polynomial::from_f64(&[-6.0, 11.0, -6.0, 1.0])
+            .solve_with_options(&SolveOptions { closed_form: false, ..SolveOptions::default() }, None);
+        assert!(report.converged && report.iterations > 0);
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_interpolation_and_fit() {
+        let vandermonde = Matrix::vandermonde(&[2.0, 3.0], 2);
+        assert_matrix_close(&vandermonde, &Matrix::init(2, 3, vec![1.0, 2.0, 4.0, 1.0, 3.0, 9.0]), 0.0);
+
+        // 三种插值方法得到同一个多项式 x^3 - 2x + 1
+        let points = [(-1.0, 2.0), (0.0, 1.0), (1.0, 0.0), (2.0, 5.0)];
+        let interpolations = [
+            Polynomial::interpolate_lagrange(&points).unwrap(),
+            Polynomial::interpolate_newton(&points).unwrap(),
+            Polynomial::interpolate_barycentric(&points).unwrap(),
+        ];
+        for polynomial in &interpolations {
+            assert_polynomial_close(polynomial, &[1.0, -2.0, 0.0, 1.0]);
+        }
+        // 共线的点不会留下舍入误差造成的高次项
+        let line = Polynomial::interpolate_lagrange(&[(0.0, 1.0), (1.0, 3.0), (3.0, 7.0)]).unwrap();
+        assert_eq!(line.degree(), 1);
+        assert_eq!(line.to_string(), "2x + 1");
+        assert!(Polynomial::interpolate_newton(&[(1.0, 2.0), (1.0, 3.0)]).is_none());
+        assert!(Polynomial::interpolate_barycentric(&[]).unwrap().is_zero());
+
+        // 精确在直线上的数据，残差为零
+        let exact = Polynomial::fit(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0)], 1).unwrap();
+        assert_polynomial_close(&exact.polynomial, &[1.0, 2.0]);
+        assert!(exact.residual_sum_of_squares < 1e-20);
+        assert!((exact.r_squared - 1.0).abs() < 1e-12);
+
+        // y = 1 + 2x 加上 ±0.1 的扰动
+        let noisy = [(0.0, 1.1), (1.0, 2.9), (2.0, 5.1), (3.0, 6.9)];
+        let fit = Polynomial::fit(&noisy, 1).unwrap();
+        assert_polynomial_close(&fit.polynomial.round_coefficients(9), &[1.06, 1.96]);
+        assert!((fit.residual_sum_of_squares - 0.032).abs() < 1e-12);
+        assert!((fit.r_squared - (1.0 - 0.032 / 19.24)).abs() < 1e-12);
+        assert!((fit.adjusted_r_squared.unwrap() - (1.0 - 0.032 / 19.24 * 3.0 / 2.0)).abs() < 1e-12);
+        assert!((fit.standard_error.unwrap() - 0.016f64.sqrt()).abs() < 1e-12);
+        assert!((fit.residuals.iter().sum::<f64>()).abs() < 1e-12);
+
+        // 数据点恰好等于系数个数时退化为插值，自由度为零
+        let interpolating = Polynomial::fit(&points, 3).unwrap();
+        assert_polynomial_close(&interpolating.polynomial.round_coefficients(9), &[1.0, -2.0, 0.0, 1.0]);
+        assert!(interpolating.standard_error.is_none() && interpolating.adjusted_r_squared.is_none());
+        assert!(Polynomial::fit(&points, 4).is_none());
+        assert!(Polynomial::fit(&[(1.0, 1.0), (1.0, 2.0), (1.0, 3.0)], 1).is_none());
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_polynomial_calculus() {
+        // x^3 + 2x^2 - x + 5
+        let cubic = Polynomial::from_f64(&[5.0, -1.0, 2.0, 1.0]);
+        assert_polynomial_close(&cubic.derivative(0), &[5.0, -1.0, 2.0, 1.0]);
+        assert_polynomial_close(&cubic.derivative(1), &[-1.0, 4.0, 3.0]);
+        assert_polynomial_close(&cubic.derivative(2), &[4.0, 6.0]);
+        assert_polynomial_close(&cubic.derivative(3), &[6.0]);
+        assert!(cubic.derivative(4).is_zero());
+
+        let primitive = Polynomial::from_f64(&[2.0, 0.0, 3.0]).antiderivative(&Complex::init(1.0, 0.0));
+        assert_polynomial_close(&primitive, &[1.0, 2.0, 0.0, 1.0]);
+        assert_polynomial_close(&cubic.antiderivative(&Complex::new()).derivative(1), &[5.0, -1.0, 2.0, 1.0]);
+        assert_polynomial_close(&Polynomial::zero().antiderivative(&Complex::init(4.0, 0.0)), &[4.0]);
+
+        // ∫_0^3 x^2 dx = 9，交换上下限变号
+        let square = Polynomial::from_f64(&[0.0, 0.0, 1.0]);
+        let (zero, three) = (BigDecimal::from(0), BigDecimal::from(3));
+        assert!((&square.integrate(&zero, &three) - &Complex::init(9.0, 0.0)).abs() < BigDecimal::from_f64(1e-30).unwrap());
+        assert!((&square.integrate(&three, &zero) - &Complex::init(-9.0, 0.0)).abs() < BigDecimal::from_f64(1e-30).unwrap());
+        // ∫_0^2 (ix + 1) dx = 2 + 2i
+        let complex: Polynomial = "ix + 1".parse().unwrap();
+        assert!((&complex.integrate(&zero, &BigDecimal::from(2)) - &Complex::init(2.0, 2.0)).abs() < BigDecimal::from_f64(1e-30).unwrap());
+
+        // (x - 1)^2 平移之后为 x^2，平移后的常数项就是 p(a)
+        assert_polynomial_close(&Polynomial::from_f64(&[1.0, -2.0, 1.0]).taylor_shift(&Complex::init(1.0, 0.0)), &[0.0, 0.0, 1.0]);
+        let shifted = cubic.taylor_shift(&Complex::init(-2.0, 0.0));
+        assert!((&shifted.get_coefficients()[0] - &cubic.evaluate(&Complex::init(-2.0, 0.0))).abs() < BigDecimal::from_f64(1e-30).unwrap());
+        assert_polynomial_close(&shifted.taylor_shift(&Complex::init(2.0, 0.0)), &[5.0, -1.0, 2.0, 1.0]);
+        assert_polynomial_close(&Polynomial::from_f64(&[1.0, 1.0, 1.0]).scale(&Complex::init(2.0, 0.0)), &[1.0, 2.0, 4.0]);
+
+        // x^3 - 3x 的驻点 ±1 都是极值点；x^4 在 0 处导数有三重根
+        let threshold = BigDecimal::from_str("0.0000000001").unwrap();
+        let points = Polynomial::from_f64(&[0.0, -3.0, 0.0, 1.0]).critical_points(&threshold);
+        assert_eq!(points.len(), 2);
+        for expected in [1.0, -1.0] {
+            assert!(points.iter().any(|(point, multiplicity)| (point - &Complex::init(expected, 0.0)).abs() < threshold && *multiplicity == 1));
+        }
+        let points = Polynomial::from_f64(&[0.0, 0.0, 0.0, 0.0, 1.0]).critical_points(&threshold);
+        assert_eq!(points.len(), 1);
+        assert!(points[0].0.abs() < threshold && points[0].1 == 3);
+        assert!(Polynomial::from_f64(&[1.0, 2.0]).critical_points(&threshold).is_empty());
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_real_roots() {
+        let cubic = Polynomial::from_f64(&[-6.0, 11.0, -6.0, 1.0]);
+        let sequence = cubic.sturm_sequence().unwrap();
+        assert_eq!(sequence.count_all_roots(), 3);
+        assert_eq!(sequence.count_roots(&BigDecimal::from(0), &BigDecimal::from(10)), 3);
+        assert_eq!(cubic.count_real_roots(&BigDecimal::from_f64(1.5).unwrap(), &BigDecimal::from_f64(2.5).unwrap()), Some(1));
+        // 区间左开右闭
+        assert_eq!(cubic.count_real_roots(&BigDecimal::from(1), &BigDecimal::from(2)), Some(1));
+        assert_eq!(Polynomial::from_f64(&[1.0, 0.0, 1.0]).sturm_sequence().unwrap().count_all_roots(), 0);
+        assert!("x^2 + i".parse::<Polynomial>().unwrap().sturm_sequence().is_none());
+
+        // 重根只算一次，Sturm 序列的最后一项是 p 与 p' 的公因式
+        let repeated = Polynomial::from_f64(&[2.0, -3.0, 0.0, 1.0]);
+        let sequence = repeated.sturm_sequence().unwrap();
+        assert_eq!(sequence.count_all_roots(), 2);
+        assert_eq!(sequence.get_polynomials().last().unwrap().degree(), 1);
+        let intervals = repeated.isolate_real_roots().unwrap();
+        assert_eq!(intervals.len(), 2);
+        assert!(intervals[0].upper < intervals[1].lower || intervals[0].upper == intervals[1].lower && !intervals[1].is_exact());
+        assert!(intervals[0].lower < -2 && intervals[0].upper > -2);
+
+        // 二分点恰好是根时得到精确的根，相邻的区间收缩到不含它
+        let intervals = Polynomial::from_f64(&[0.0, -1.0, 1.0]).isolate_real_roots().unwrap();
+        assert_eq!(intervals.len(), 2);
+        assert!(intervals[0].is_exact() && intervals[0].lower == 0);
+        assert!(intervals[1].lower > 0 && intervals[1].lower < 1 && intervals[1].upper > 1);
+        assert_eq!(intervals[0].to_string(), "0");
+
+        // x^2 - 2 的正根细化到 40 位
+        let intervals = Polynomial::from_f64(&[-2.0, 0.0, 1.0]).real_roots(40).unwrap();
+        assert_eq!(intervals.len(), 2);
+        let sqrt2 = BigDecimal::from(2).sqrt().unwrap();
+        assert!(intervals[1].width() <= BigDecimal::from_str("1e-40").unwrap());
+        assert!(intervals[1].lower < sqrt2 && sqrt2 < intervals[1].upper);
+        assert!((intervals[0].midpoint() + &sqrt2).abs() < BigDecimal::from_str("1e-40").unwrap());
+
+        // 靠得很近的两个根也能分开，并且与 refine_real_root 的结果一致
+        let close = Polynomial::from_f64(&[1.001, -2.001, 1.0]);
+        let intervals = close.isolate_real_roots().unwrap();
+        assert_eq!(intervals.len(), 2);
+        for (interval, root) in intervals.iter().zip([1.0, 1.001]) {
+            let refined = close.refine_real_root(interval, 20).unwrap();
+            assert!(refined.lower >= interval.lower && refined.upper <= interval.upper);
+            assert!((refined.midpoint() - BigDecimal::from_f64(root).unwrap()).abs() < BigDecimal::from_f64(1e-6).unwrap());
+        }
+        assert_eq!(Polynomial::from_f64(&[3.0]).isolate_real_roots().map(|intervals| intervals.len()), Some(0));
+    }
+
+    #[test]
+    fn test_matrix_pow() {
+        // 斐波那契矩阵
+        let fibonacci = Matrix::init(2, 2, vec![1i64, 1, 1, 0]);
+        let result = Matrix::pow(&fibonacci, 10);
+        assert_eq!(*result.get(0, 0), 89);
+        assert_eq!(*result.get(0, 1), 55);
+        assert_eq!(*result.get(1, 1), 34);
+        let identity = Matrix::pow(&fibonacci, 0);
+        assert_eq!(*identity.get(0, 0), 1);
+        assert_eq!(*identity.get(0, 1), 0);
+    }
+
+    #[test]
+    fn test_expm_rotation_generator() {
+        let theta = 1.2f64;
+        let generator = Matrix::init(2, 2, vec![0.0, -theta, theta, 0.0]);
+        let expected = Matrix::init(2, 2, vec![theta.cos(), -theta.sin(), theta.sin(), theta.cos()]);
+        assert_matrix_close(&Matrix::expm(&generator).unwrap(), &expected, 1e-12);
+        // 范数较大时需要缩放平方
+        let large = &generator * 10.0;
+        let expected = Matrix::init(2, 2, vec![12f64.cos(), -12f64.sin(), 12f64.sin(), 12f64.cos()]);
+        assert_matrix_close(&Matrix::expm(&large).unwrap(), &expected, 1e-10);
+        assert_matrix_close(&Matrix::logm(&Matrix::expm(&generator).unwrap()).unwrap(), &generator, 1e-10);
+    }
+
+    #[test]
+    fn test_expm_nilpotent() {
+        // N^3 = 0, exp(N) = I + N + N^2 / 2
+        let nilpotent = Matrix::init(3, 3, vec![0.0, 1.0, 2.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0]);
+        let expected = Matrix::init(3, 3, vec![1.0, 1.0, 3.5, 0.0, 1.0, 3.0, 0.0, 0.0, 1.0]);
+        assert_matrix_close(&Matrix::expm(&nilpotent).unwrap(), &expected, 1e-12);
+    }
+
+    #[test]
+    fn test_matrix_functions_diagonalizable() {
+        // A = P D P^-1, D = diag(1, 4)
+        let p = Matrix::init(2, 2, vec![1.0, 1.0, 1.0, 2.0]);
+        let p_inverse = Matrix::init(2, 2, vec![2.0, -1.0, -1.0, 1.0]);
+        let build = |d1: f64, d2: f64| &(&p * &Matrix::init(2, 2, vec![d1, 0.0, 0.0, d2])) * &p_inverse;
+        let a = build(1.0, 4.0);
+        assert_matrix_close(&Matrix::expm(&a).unwrap(), &build(1f64.exp(), 4f64.exp()), 1e-9);
+        assert_matrix_close(&Matrix::sqrtm(&a).unwrap(), &build(1.0, 2.0), 1e-10);
+        assert_matrix_close(&Matrix::logm(&a).unwrap(), &build(0.0, 4f64.ln()), 1e-10);
+        assert_matrix_close(&Matrix::powf(&a, 1.5).unwrap(), &build(1.0, 8.0), 1e-9);
+        assert_matrix_close(&Matrix::powi(&a, -2).unwrap(), &build(1.0, 1.0 / 16.0), 1e-12);
+
+        // 奇异矩阵和有负特征值的矩阵没有实的平方根与对数，返回 None 而不是 panic
+        let singular = Matrix::init(2, 2, vec![0.0, 0.0, 0.0, 0.0]);
+        assert!(Matrix::sqrtm(&singular).is_none());
+        assert!(Matrix::logm(&singular).is_none());
+        assert!(Matrix::powf(&singular, 0.5).is_none());
+        assert!(Matrix::powi(&singular, -1).is_none());
+        let negative = Matrix::init(2, 2, vec![-1.0, 0.0, 0.0, 1.0]);
+        assert!(Matrix::sqrtm(&negative).is_none());
+        assert!(Matrix::powf(&negative, 0.5).is_none());
+    }
+
+    #[test]
+    fn test_kronecker_and_direct_sum() {
+        let a = Matrix::init(2, 2, vec![1i64, 2, 3, 4]);
+        let b = Matrix::init(1, 2, vec![0i64, 5]);
+        let product = Matrix::kronecker(&a, &b);
+        assert_eq!((product.get_rows(), product.get_cols()), (2, 4));
+        let expected = [0, 5, 0, 10, 0, 15, 0, 20];
+        for (index, value) in expected.iter().enumerate() {
+            assert_eq!(*product.get(index / 4, index % 4), *value);
+        }
+        let sum = Matrix::direct_sum(&a, &b);
+        assert_eq!((sum.get_rows(), sum.get_cols()), (3, 4));
+        assert_eq!(*sum.get(1, 1), 4);
+        assert_eq!(*sum.get(2, 3), 5);
+        assert_eq!(*sum.get(0, 3), 0);
+    }
+
+    #[test]
+    fn test_vec_and_commutation_matrix() {
+        let a = Matrix::init(2, 3, vec![1i64, 2, 3, 4, 5, 6]);
+        let vectorized = Matrix::vec(&a);
+        let expected = [1, 4, 2, 5, 3, 6];
+        for (index, value) in expected.iter().enumerate() {
+            assert_eq!(*vectorized.get(index, 0), *value);
+        }
+        let restored = Matrix::unvec(&vectorized, 2);
+        assert_eq!(*restored.get(1, 2), 6);
+        let commutation: Matrix<i64> = Matrix::commutation_matrix(2, 3);
+        let transposed = &commutation * &vectorized;
+        let expected = Matrix::vec(&Matrix::transpose(&a));
+        for index in 0..6 {
+            assert_eq!(*transposed.get(index, 0), *expected.get(index, 0));
+        }
+    }
+
+    #[test]
+    fn test_sylvester_and_lyapunov() {
+        let a = Matrix::init(2, 2, vec![1.0, 2.0, 0.0, 3.0]);
+        let b = Matrix::init(3, 3, vec![4.0, 0.0, 1.0, 1.0, 5.0, 0.0, 0.0, 1.0, 6.0]);
+        let x = Matrix::init(2, 3, vec![1.0, -1.0, 2.0, 0.5, 3.0, -2.0]);
+        let c = &(&a * &x) + &(&x * &b);
+        assert_matrix_close(&Matrix::sylvester(&a, &b, &c).unwrap(), &x, 1e-10);
+
+        let stable = Matrix::init(2, 2, vec![-1.0, 0.5, 0.0, -2.0]);
+        let q = Matrix::init(2, 2, vec![-1.0, 0.0, 0.0, -1.0]);
+        let solution = Matrix::lyapunov(&stable, &q).unwrap();
+        let residual = &(&stable * &solution) + &(&solution * &Matrix::transpose(&stable));
+        assert_matrix_close(&residual, &q, 1e-10);
+        // A 与 -B 有公共特征值时无唯一解
+        let singular = Matrix::init(1, 1, vec![1.0]);
+        let negated = Matrix::init(1, 1, vec![-1.0]);
+        assert!(Matrix::sylvester(&singular, &negated, &singular).is_none());
+    }
+
+    #[test]
+    fn test_parse_matrix_literals() {
+        let matlab: Matrix<f64> = "[1 2.5; -3, 4]".parse().unwrap();
+        assert_matrix_close(&matlab, &Matrix::init(2, 2, vec![1.0, 2.5, -3.0, 4.0]), 1e-12);
+        let nested: Matrix<i64> = "[[1, 2, 3], [4, 5, 6]]".parse().unwrap();
+        assert_eq!((nested.get_rows(), nested.get_cols()), (2, 3));
+        assert_eq!(*nested.get(1, 2), 6);
+        let boxed: Matrix<f64> = "┌          ┐\n│  1.000,    2.000,  │\n│  3.000,   -4.000,  │\n└          ┘".parse().unwrap();
+        assert_matrix_close(&boxed, &Matrix::init(2, 2, vec![1.0, 2.0, 3.0, -4.0]), 1e-12);
+        let two_boxes = "┌   ┐\n│ 1 │\n└   ┘\n┌   ┐\n│ 2 │\n└   ┘\n";
+        assert!(matches!(two_boxes.parse::<Matrix<f64>>(), Err(ParseMatrixError::MultipleMatrices(2))));
+
+        assert!(matches!("[1 2; 3]".parse::<Matrix<f64>>(), Err(ParseMatrixError::RaggedRow { row: 1, expected: 2, found: 1 })));
+        assert!(matches!("[1 x; 3 4]".parse::<Matrix<f64>>(), Err(ParseMatrixError::InvalidElement { row: 0, col: 1, .. })));
+        assert!(matches!("[[1, 2], [3, 4]".parse::<Matrix<f64>>(), Err(ParseMatrixError::UnbalancedBrackets)));
+        assert!(matches!("[]".parse::<Matrix<f64>>(), Err(ParseMatrixError::Empty)));
+    }
+
+    #[test]
+    fn test_write_to_file_round_trip() {
+        let file_name = std::env::temp_dir().join("matrix_round_trip_test.txt").to_string_lossy().to_string();
+        let _ = std::fs::remove_file(&file_name);
+        let first = Matrix::init(2, 2, vec![1.5, -2.25, 3.0, 4.0]);
+        let second = Matrix::init(1, 3, vec![7.0, 8.0, 9.0]);
+        Matrix::write_to_file(&first, &file_name, 10, 8).unwrap();
+        Matrix::write_to_file(&second, &file_name, 10, 8).unwrap();
+        let matrices: Vec<Matrix<f64>> = Matrix::read_from_file(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+        assert_eq!(matrices.len(), 2);
+        assert_matrix_close(&matrices[0], &first, 1e-12);
+        assert_matrix_close(&matrices[1], &second, 1e-12);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let matrix = Matrix::init(2, 3, vec![1.5, -2.0, 3.25, 0.0, 1e-7, 42.0]);
+        let options = CsvOptions {
+            delimiter: ';',
+            has_header: true,
+            column_names: vec!["x".to_string(), "y".to_string()],
+        };
+        let mut buffer: Vec<u8> = Vec::new();
+        Matrix::write_csv(&matrix, &mut buffer, &options).unwrap();
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        assert!(text.starts_with("x;y;c2\n1.5;-2;3.25\n"));
+        let restored: Matrix<f64> = Matrix::read_csv(buffer.as_slice(), &options).unwrap();
+        assert_matrix_close(&restored, &matrix, 0.0);
+        let ragged = "1,2\n3\n".as_bytes();
+        assert!(matches!(Matrix::<f64>::read_csv(ragged, &CsvOptions::default()), Err(ParseMatrixError::RaggedRow { row: 1, .. })));
+    }
+
+    #[test]
+    fn test_matrix_market() {
+        let text = "%%MatrixMarket matrix coordinate integer symmetric\n% comment\n3 3 3\n1 1 5\n3 1 -2\n2 2 7\n";
+        let symmetric: Matrix<i64> = Matrix::read_matrix_market(text.as_bytes()).unwrap();
+        assert_eq!(*symmetric.get(0, 2), -2);
+        assert_eq!(*symmetric.get(2, 0), -2);
+        assert_eq!(*symmetric.get(1, 1), 7);
+        assert_eq!(*symmetric.get(2, 2), 0);
+
+        let dense = Matrix::init(2, 3, vec![1.0, 0.0, 2.5, 0.0, -4.0, 0.0]);
+        for format in [MarketFormat::Array, MarketFormat::Coordinate] {
+            let mut buffer: Vec<u8> = Vec::new();
+            Matrix::write_matrix_market(&dense, &mut buffer, format).unwrap();
+            let restored: Matrix<f64> = Matrix::read_matrix_market(buffer.as_slice()).unwrap();
+            assert_matrix_close(&restored, &dense, 0.0);
+        }
+
+        let hermitian = "%%MatrixMarket matrix coordinate complex hermitian\n2 2 2\n1 1 1.0 0.0\n2 1 2.0 3.0\n";
+        let (real, imaginary) = Matrix::read_matrix_market_complex(hermitian.as_bytes()).unwrap();
+        assert_eq!(*real.get(0, 1), 2.0);
+        assert_eq!(*imaginary.get(1, 0), 3.0);
+        assert_eq!(*imaginary.get(0, 1), -3.0);
+        let mut buffer: Vec<u8> = Vec::new();
+        Matrix::write_matrix_market_complex(&real, &imaginary, &mut buffer, MarketFormat::Array).unwrap();
+        let (real_back, imaginary_back) = Matrix::read_matrix_market_complex(buffer.as_slice()).unwrap();
+        assert_matrix_close(&real_back, &real, 0.0);
+        assert_matrix_close(&imaginary_back, &imaginary, 0.0);
+        assert!(matches!(Matrix::<f64>::read_matrix_market(hermitian.as_bytes()), Err(ParseMatrixError::InvalidHeader(_))));
+
+        let empty: Matrix<f64> = Matrix::read_matrix_market("%%MatrixMarket matrix array real symmetric\n0 0\n".as_bytes()).unwrap();
+        assert_eq!((empty.get_rows(), empty.get_cols()), (0, 0));
+        let overflowing = "%%MatrixMarket matrix coordinate real general\n18446744073709551615 2 0\n";
+        assert!(matches!(Matrix::<f64>::read_matrix_market(overflowing.as_bytes()), Err(ParseMatrixError::InvalidHeader(_))));
+        for not_square in ["%%MatrixMarket matrix coordinate real symmetric\n2 3 1\n1 3 5\n",
+                           "%%MatrixMarket matrix array real symmetric\n3 2\n1\n2\n3\n4\n5\n"] {
+            assert!(matches!(Matrix::<f64>::read_matrix_market(not_square.as_bytes()), Err(ParseMatrixError::InvalidHeader(_))));
+        }
+        let hermitian_not_square = "%%MatrixMarket matrix coordinate complex hermitian\n2 3 1\n1 3 1.0 2.0\n";
+        assert!(matches!(Matrix::read_matrix_market_complex(hermitian_not_square.as_bytes()), Err(ParseMatrixError::InvalidHeader(_))));
+    }
+
+    #[test]
+    fn test_npy_round_trip() {
+        let matrix = Matrix::init(2, 3, vec![1i64, -2, 3, 4, 5, i64::MAX]);
+        let mut buffer: Vec<u8> = Vec::new();
+        Matrix::write_npy(&matrix, &mut buffer).unwrap();
+        assert_eq!(&buffer[..8], b"\x93NUMPY\x01\x00");
+        let header_length = u16::from_le_bytes([buffer[8], buffer[9]]) as usize;
+        assert_eq!((10 + header_length) % 64, 0);
+        assert_eq!(buffer.len(), 10 + header_length + 6 * 8);
+        let restored: Matrix<i64> = Matrix::read_npy(&mut buffer.as_slice()).unwrap();
+        assert_eq!(*restored.get(1, 2), i64::MAX);
+        assert!(Matrix::<f64>::read_npy(&mut buffer.as_slice()).is_err());
+
+        // 手工构造的 2.0 版本、列优先文件
+        let dictionary = "{'descr': '<f8', 'fortran_order': True, 'shape': (2, 2), }\n";
+        let mut file: Vec<u8> = b"\x93NUMPY\x02\x00".to_vec();
+        file.extend_from_slice(&(dictionary.len() as u32).to_le_bytes());
+        file.extend_from_slice(dictionary.as_bytes());
+        for value in [1.0f64, 3.0, 2.0, 4.0] {
+            file.extend_from_slice(&value.to_le_bytes());
+        }
+        let restored: Matrix<f64> = Matrix::read_npy(&mut file.as_slice()).unwrap();
+        assert_matrix_close(&restored, &Matrix::init(2, 2, vec![1.0, 2.0, 3.0, 4.0]), 0.0);
+
+        let npy = |shape: &str| {
+            let dictionary = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {}, }}\n", shape);
+            let mut file: Vec<u8> = b"\x93NUMPY\x01\x00".to_vec();
+            file.extend_from_slice(&(dictionary.len() as u16).to_le_bytes());
+            file.extend_from_slice(dictionary.as_bytes());
+            file.extend_from_slice(&1.0f64.to_le_bytes());
+            return file;
+        };
+        let overflowing = npy("(18446744073709551615, 2)");
+        assert!(matches!(Matrix::<f64>::read_npy(&mut overflowing.as_slice()), Err(ParseMatrixError::InvalidHeader(_))));
+        let truncated = npy("(4294967296, 4294967295)");
+        assert!(matches!(Matrix::<f64>::read_npy(&mut truncated.as_slice()), Err(ParseMatrixError::Io(_))));
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let matrix = Matrix::init(3, 2, vec![0.1, -2.0, f64::MAX, 4.5, f64::MIN_POSITIVE, 6.0]);
+        let file_name = std::env::temp_dir().join("matrix_binary_test.bin").to_string_lossy().to_string();
+        Matrix::save_binary(&matrix, &file_name).unwrap();
+        let restored: Matrix<f64> = Matrix::load_binary(&file_name).unwrap();
+        assert!(matches!(Matrix::<i64>::load_binary(&file_name), Err(ParseMatrixError::InvalidHeader(_))));
+        std::fs::remove_file(&file_name).unwrap();
+        assert_matrix_close(&restored, &matrix, 0.0);
+
+        let mut buffer: Vec<u8> = Vec::new();
+        Matrix::write_binary(&Matrix::init(1, 2, vec![7i64, -8]), &mut buffer).unwrap();
+        assert_eq!(&buffer[..8], b"MATB\x01\x00\x02\x00");
+        assert_eq!(buffer.len(), 24 + 2 * 8);
+
+        // 文件头声称的大小溢出或者远大于实际内容时报错，而不是先分配内存
+        buffer[8..24].copy_from_slice(&[0xff; 16]);
+        assert!(matches!(Matrix::<i64>::read_binary(&mut buffer.as_slice()), Err(ParseMatrixError::InvalidHeader(_))));
+        buffer[8..24].copy_from_slice(&[[0xff; 4], [0xff, 0xff, 0xff, 0x0f], [1, 0, 0, 0], [0; 4]].concat());
+        assert!(matches!(Matrix::<i64>::read_binary(&mut buffer.as_slice()), Err(ParseMatrixError::Io(_))));
+    }
+
+    #[cfg(all(feature = "parallel", feature = "rand"))]
+    #[test]
+    fn test_par_multiply() {
+        let lhs = crate::matrix::random_f64_matrix(17, 9, 5, -1.0, 1.0);
+        let rhs = crate::matrix::random_f64_matrix(9, 13, 6, -1.0, 1.0);
+        assert_matrix_close(&Matrix::par_multiply(&lhs, &rhs), &(&lhs * &rhs), 0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let matrix = Matrix::init(2, 2, vec![1.5, -2.0, 3.0, 4.0]);
+        let json = serde_json::to_string(&matrix).unwrap();
+        assert_eq!(json, r#"{"rows":2,"cols":2,"data":[1.5,-2.0,3.0,4.0]}"#);
+        let restored: Matrix<f64> = serde_json::from_str(&json).unwrap();
+        assert_matrix_close(&restored, &matrix, 0.0);
+        assert!(serde_json::from_str::<Matrix<f64>>(r#"{"rows":2,"cols":2,"data":[1.0]}"#).is_err());
+        // 2^32 * 2^32 在 64 位下回绕为 0，不能被当作空矩阵接受
+        assert!(serde_json::from_str::<Matrix<f64>>(r#"{"rows":4294967296,"cols":4294967296,"data":[]}"#).is_err());
+    }
+
+    #[cfg(all(feature = "serde", feature = "bigdecimal"))]
+    #[test]
+    fn test_serde_big_decimal_round_trip() {
+        let precise = BigDecimal::from_str("0.1234567890123456789012345678901234567890").unwrap();
+        let complex = Complex::from_big_decimal(&precise, &BigDecimal::from(-3));
+        let json = serde_json::to_string(&complex).unwrap();
+        assert_eq!(json, r#"{"real":"0.1234567890123456789012345678901234567890","imaginary":"-3"}"#);
+        let restored: Complex = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.real, precise);
+
+        let equation = EquationWithDegrees::new(vec![Complex::init(-1.0, 0.0), Complex::init(1.0, 0.0)]);
+        let json = serde_json::to_string(&equation).unwrap();
+        let restored: EquationWithDegrees = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+    }
+
+    #[test]
+    fn test_matrix_formatter() {
+        let matrix = Matrix::init(2, 2, vec![1.0, -22.5, 300.0, 4.0]);
+        assert_eq!(format!("{}", matrix), "┌           ┐\n│   1 -22.5 │\n│ 300     4 │\n└           ┘\n");
+        assert_eq!(format!("{:.1}", matrix), "┌             ┐\n│   1.0 -22.5 │\n│ 300.0   4.0 │\n└             ┘\n");
+        let reparsed: Matrix<f64> = format!("{}", matrix).parse().unwrap();
+        assert_matrix_close(&reparsed, &matrix, 0.0);
+
+        let latex = MatrixFormatter { style: OutputStyle::Latex, ..MatrixFormatter::default() };
+        assert_eq!(latex.format(&matrix), "\\begin{bmatrix}\n  1 & -22.5 \\\\\n300 &     4\n\\end{bmatrix}\n");
+        let markdown = MatrixFormatter {
+            style: OutputStyle::Markdown,
+            precision: Some(0),
+            alignment: Alignment::Left,
+            ..MatrixFormatter::default()
+        };
+        assert_eq!(markdown.format(&matrix), "| c0  | c1  |\n| :-- | :-- |\n| 1   | -22 |\n| 300 | 4   |\n");
+        let html = MatrixFormatter { style: OutputStyle::Html, ..MatrixFormatter::default() };
+        assert!(html.format(&matrix).contains("<tr><td style=\"text-align: right\">1</td><td style=\"text-align: right\">-22.5</td></tr>"));
+        let mut buffer: Vec<u8> = Vec::new();
+        let fixed = MatrixFormatter { width: Some(6), alignment: Alignment::Center, ..MatrixFormatter::default() };
+        fixed.write(&Matrix::init(1, 1, vec![7i64]), &mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "┌        ┐\n│   7    │\n└        ┘\n");
+    }
+
+    #[test]
+    fn test_rref() {
+        let matrix = Matrix::init(3, 4, vec![1.0, 2.0, -1.0, -4.0, 2.0, 3.0, -1.0, -11.0, -2.0, 0.0, -3.0, 22.0]);
+        let expected = Matrix::init(3, 4, vec![1.0, 0.0, 0.0, -8.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, -2.0]);
+        assert_matrix_close(&Matrix::rref(&matrix), &expected, 1e-12);
+        let rank_deficient = Matrix::init(2, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0]);
+        let expected = Matrix::init(2, 3, vec![1.0, 2.0, 3.0, 0.0, 0.0, 0.0]);
+        assert_matrix_close(&Matrix::rref(&rank_deficient), &expected, 1e-12);
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_repl_session() {
+        let mut session = Session::new();
+        assert!(matches!(session.execute("A = [2 1; 1 1]"), Ok(Output::Assigned(_, Value::Matrix(_)))));
+        session.execute("B = [[1, 2], [3, 4]]").unwrap();
+        match session.execute("inv(A) * B'") {
+            Ok(Output::Value(Value::Matrix(result))) =>
+                assert_matrix_close(&result, &Matrix::init(2, 2, vec![-1.0, -1.0, 3.0, 5.0]), 1e-12),
+            _ => panic!("应得到矩阵"),
+        }
+        assert!(matches!(session.execute("det(A) * 2 - 1"), Ok(Output::Value(Value::Scalar(value))) if (value - 1.0).abs() < 1e-12));
+        match session.execute("z = (1 + 2i) * (3 - 4i)") {
+            Ok(Output::Assigned(_, Value::Complex(z))) => {
+                assert_eq!(z.real, BigDecimal::from(11));
+                assert_eq!(z.imaginary, BigDecimal::from(2));
+            }
+            _ => panic!("应得到复数"),
+        }
+        match session.execute("roots(x^2 - 1)") {
+            Ok(Output::Value(Value::Roots(roots))) => assert_eq!(roots.len(), 2),
+            _ => panic!("应得到根"),
+        }
+        assert!(session.execute("A * [1 2 3]").err().unwrap().contains("矩阵大小不匹配"));
+        assert!(session.execute("inv([1 2; 2 4])").err().unwrap().contains("奇异"));
+        assert!(session.execute("C + 1").err().unwrap().contains("未定义"));
+        assert!(matches!(session.execute("!2"), Ok(Output::Assigned(name, _)) if name == "B"));
+        session.execute("p = (1 + 2i) * x^2 - 0.5").unwrap();
+
+        let file_name = std::env::temp_dir().join("matrix_repl_test.txt").to_string_lossy().to_string();
+        session.execute(&format!("save {}", file_name)).unwrap();
+        let mut restored = Session::new();
+        restored.execute("x = 5").unwrap();
+        restored.execute(&format!("load {}", file_name)).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+        // 读取的文件中只允许赋值语句，读取自身的 load 命令会报错而不是无限递归
+        let recursive = std::env::temp_dir().join("matrix_repl_recursive.txt").to_string_lossy().to_string();
+        std::fs::write(&recursive, format!("a = 1\nload {}\n", recursive)).unwrap();
+        let error = restored.execute(&format!("load {}", recursive)).err().unwrap();
+        std::fs::remove_file(&recursive).unwrap();
+        assert!(error.contains("第 2 行") && error.contains("只能包含赋值语句"), "{}", error);
+        match (restored.get_variable("A"), restored.get_variable("z")) {
+            (Some(Value::Matrix(a)), Some(Value::Complex(z))) => {
+                assert_matrix_close(a, &Matrix::init(2, 2, vec![2.0, 1.0, 1.0, 1.0]), 0.0);
+                assert_eq!(z.imaginary, BigDecimal::from(2));
+            }
+            _ => panic!("变量没有被还原"),
+        }
+        match (session.get_variable("p"), restored.get_variable("p")) {
+            (Some(Value::Polynomial(saved)), Some(Value::Polynomial(loaded))) => assert_eq!(format!("{}", saved), format!("{}", loaded)),
+            _ => panic!("多项式没有被还原"),
+        }
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_expression_evaluation() {
+        let mut interpreter = Interpreter::new();
+        let script = "# 脚本中的注释\nA = [1 -2; 3 4]\nB = A .* A - 2 * A'\nc = -2^2 + 3 * (1 - 4) / 2\n";
+        interpreter.run(script).unwrap();
+        match interpreter.get_variable("B") {
+            Some(Value::Matrix(b)) => assert_matrix_close(b, &Matrix::init(2, 2, vec![-1.0, -2.0, 13.0, 8.0]), 1e-12),
+            _ => panic!("B 应为矩阵"),
+        }
+        assert!(matches!(interpreter.get_variable("c"), Some(Value::Scalar(c)) if (*c + 8.5).abs() < 1e-12));
+        assert!(matches!(interpreter.run("det(A^2)").unwrap(), Some(Value::Scalar(d)) if (d - 100.0).abs() < 1e-9));
+        assert!(matches!(interpreter.run("det([-1, 2; 0, 3])").unwrap(), Some(Value::Scalar(d)) if (d + 3.0).abs() < 1e-12));
+        assert!(matches!(interpreter.run("[1 - 2]").unwrap(), Some(Value::Matrix(m)) if m.get_cols() == 1));
+        assert!(matches!(interpreter.run("abs(exp(0.5i) * 2)").unwrap(), Some(Value::Scalar(r)) if (r - 2.0).abs() < 1e-9));
+        match interpreter.run("p = (x - 1) * (x + 2)\neval(p, 3)").unwrap() {
+            Some(Value::Complex(value)) => assert_eq!(value.real, BigDecimal::from(10)),
+            _ => panic!("应为复数"),
+        }
+        match interpreter.run("2x^3 + 6x^2 - 2x - 1").unwrap() {
+            Some(Value::Polynomial(polynomial)) => {
+                let coefficients: Vec<BigDecimal> = polynomial.get_parameters().iter().map(|c| c.real.clone()).collect();
+                assert_eq!(coefficients, vec![BigDecimal::from(-1), BigDecimal::from(-2), BigDecimal::from(6), BigDecimal::from(2)]);
+            }
+            _ => panic!("应为多项式"),
+        }
+        match interpreter.run("(x + 1)^5").unwrap() {
+            Some(Value::Polynomial(polynomial)) => assert_eq!(format!("{}", polynomial), "x^5 + 5x^4 + 10x^3 + 10x^2 + 5x + 1"),
+            _ => panic!("应为多项式"),
+        }
+        assert!(matches!(interpreter.run("(1.1 + 0.1i)^20000").unwrap(), Some(Value::Complex(_))));
+        let error = interpreter.run("(x + 1)^4294967296").err().unwrap();
+        assert!(matches!(error.kind, ErrorKind::InvalidArgument(_)));
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_expression_errors() {
+        let mut interpreter = Interpreter::new();
+        interpreter.run("A = [1 2; 3 4]\nv = [1 2 3]").unwrap();
+        let error = interpreter.run("A * v").err().unwrap();
+        assert!(matches!(error.kind, ErrorKind::DimensionMismatch { lhs: (2, 2), rhs: (1, 3), .. }));
+        assert_eq!(error.span, Span::new(0, 5));
+        let error = interpreter.run("1 + foo").err().unwrap();
+        assert_eq!(error.kind, ErrorKind::UndefinedVariable("foo".to_string()));
+        assert_eq!(error.span, Span::new(4, 7));
+        assert_eq!(error.render("1 + foo"), "第 1 行: 未定义的变量 foo\n  1 + foo\n      ^^^");
+        let error = interpreter.run("det(v)").err().unwrap();
+        assert!(matches!(error.kind, ErrorKind::NotSquare { rows: 1, cols: 3, .. }));
+        assert!(matches!(interpreter.run("inv([1 2; 2 4])").err().unwrap().kind, ErrorKind::SingularMatrix));
+        for text in ["[0 0; 0 0]^0.5", "[-1 0; 0 1]^0.5"] {
+            assert!(matches!(interpreter.run(text).err().unwrap().kind, ErrorKind::InvalidArgument(_)));
+        }
+        assert!(matches!(interpreter.run("[1 2; 2 4]^-1").err().unwrap().kind, ErrorKind::SingularMatrix));
+        assert!(matches!(interpreter.run("exp([1 0; 0 2])").unwrap(), Some(Value::Matrix(_))));
+        assert!(matches!(interpreter.run("A + (1 + 2i)").err().unwrap().kind, ErrorKind::TypeMismatch { .. }));
+        assert!(matches!(interpreter.run("(1 + 2i) / 0").err().unwrap().kind, ErrorKind::DivisionByZero));
+        assert!(matches!(interpreter.run("det(A, A)").err().unwrap().kind, ErrorKind::ArgumentCount { expected: 1, found: 2, .. }));
+        let error = interpreter.run("2 * (3 + ").err().unwrap();
+        assert_eq!(error.kind, ErrorKind::UnexpectedEnd);
+        let error = interpreter.run("1 $ 2").err().unwrap();
+        assert_eq!((error.kind, error.span), (ErrorKind::UnexpectedCharacter('$'), Span::new(2, 3)));
+    }
+
+    #[test]
+    fn test_operator_combinations() {
+        let a = Matrix::init(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+        let b = Matrix::init(2, 2, vec![0.5, -1.0, 2.0, 0.0]);
+        let sum = &a + &b;
+        assert_eq!(a.clone() + b.clone(), sum);
+        assert_eq!(a.clone() + &b, sum);
+        assert_eq!(&a + b.clone(), sum);
+        let difference = &a - &b;
+        assert_eq!(a.clone() - b.clone(), difference);
+        assert_eq!(&a - b.clone(), difference);
+        let product = &a * &b;
+        assert_eq!(a.clone() * b.clone(), product);
+        assert_eq!(a.clone() * &b, product);
+        assert_eq!(&a * b.clone(), product);
+
+        let mut c = a.clone();
+        c += &b;
+        c -= b.clone();
+        c *= 4.0;
+        c /= 2.0;
+        assert_eq!(c, 2.0 * &a);
+        assert_eq!(&a * 2.0, a.clone() * 2.0);
+        assert_eq!(&a / 2.0, Matrix::init(2, 2, vec![0.5, 1.0, 1.5, 2.0]));
+        assert_eq!(-&a + a.clone(), Matrix::new(2, 2));
+        assert_eq!(-Matrix::init(1, 2, vec![1, -2]), 3 * Matrix::init(1, 2, vec![-1, 2]) - Matrix::init(1, 2, vec![-2, 4]));
+
+        assert_ne!(a, Matrix::transpose(&a));
+        assert_ne!(Matrix::<f64>::new(2, 3), Matrix::new(3, 2));
+        let nearly = &a + &Matrix::init(2, 2, vec![1e-10, 0.0, -1e-10, 0.0]);
+        assert!(Matrix::approx_eq(&a, &nearly, 1e-9));
+        assert!(!Matrix::approx_eq(&a, &nearly, 1e-11));
+        assert!(!Matrix::approx_eq(&a, &Matrix::new(2, 3), 1.0));
+    }
+
+    #[test]
+    fn test_elementary_operations() {
+        let mut matrix = Matrix::init(3, 3, vec![1, 2, 3, 4, 5, 6, 7, 8, 10]);
+        matrix.swap_rows(0, 2);
+        matrix.scale_row(1, 2);
+        matrix.add_row_multiple(2, 0, -1);
+        assert_eq!(matrix, Matrix::init(3, 3, vec![7, 8, 10, 8, 10, 12, -6, -6, -7]));
+        matrix.permute_rows(&[2, 0, 1]);
+        matrix.swap_cols(0, 1);
+        matrix.scale_col(2, -1);
+        matrix.add_col_multiple(0, 1, 1);
+        matrix.permute_cols(&[1, 2, 0]);
+        assert_eq!(matrix, Matrix::init(3, 3, vec![-6, 7, -12, 7, -10, 15, 8, -12, 18]));
+
+        // 每个初等变换都等于乘以对应的初等矩阵
+        let original = Matrix::init(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        let operations = vec![
+            ElementaryOperation::SwapRows(0, 1),
+            ElementaryOperation::ScaleRow { row: 1, factor: 3 },
+            ElementaryOperation::AddRowMultiple { target: 0, source: 1, factor: -2 },
+            ElementaryOperation::PermuteRows(vec![1, 0]),
+            ElementaryOperation::SwapCols(0, 2),
+            ElementaryOperation::ScaleCol { col: 1, factor: -1 },
+            ElementaryOperation::AddColMultiple { target: 2, source: 0, factor: 5 },
+            ElementaryOperation::PermuteCols(vec![2, 0, 1]),
+        ];
+        for operation in operations {
+            let mut applied = original.clone();
+            operation.apply(&mut applied);
+            let expected = if operation.is_row_operation() {
+                &operation.to_matrix(2) * &original
+            } else {
+                &original * &operation.to_matrix(3)
+            };
+            assert_eq!(applied, expected, "{}", operation);
+        }
+        assert_eq!(ElementaryOperation::AddRowMultiple { target: 2, source: 0, factor: -0.5 }.to_string(), "R3 <- R3 + (-0.5) R1");
+
+        let target = Matrix::init(3, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]);
+        let mut recorder = ElementaryRecorder::new();
+        let (upper, swap_ops) = Matrix::get_upper_triangular_matrix_recorded(&target, &mut recorder);
+        assert_eq!((upper.clone(), swap_ops), Matrix::get_upper_triangular_matrix(&target));
+        let swaps = recorder.get_operations().iter().filter(|operation| matches!(operation, ElementaryOperation::SwapRows(..))).count();
+        assert_eq!(swaps as i64, swap_ops);
+        assert_eq!(recorder.replay(&target).last().unwrap(), &upper);
+        let mut product = target.clone();
+        for elementary in recorder.elementary_matrices(3, 3) {
+            product = &elementary * &product;
+        }
+        assert_matrix_close(&product, &upper, 1e-12);
+    }
+
+    #[test]
+    fn test_elimination_trace() {
+        let target = Matrix::init(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+        let (upper, swap_ops, trace) = Matrix::get_upper_triangular_matrix_traced(&target);
+        assert_eq!((upper.clone(), swap_ops), Matrix::get_upper_triangular_matrix(&target));
+        assert_eq!(trace.get_final(), &upper);
+        assert_eq!(trace.get_steps()[0], TraceStep::Pivot { col: 0, row: 1, value: 3.0 });
+        // 负的首元按绝对值选主元，不会把零当成主元
+        let negative = Matrix::init(2, 2, vec![-1.0, 2.0, 0.0, 3.0]);
+        assert_eq!(Matrix::determinant(&negative), -3.0);
+        let (_, _, negative_trace) = Matrix::get_upper_triangular_matrix_traced(&negative);
+        assert_eq!(negative_trace.get_steps()[0], TraceStep::Pivot { col: 0, row: 0, value: -1.0 });
+        assert_eq!(Matrix::determinant(&Matrix::init(3, 3, vec![0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0])), 1.0);
+        assert_eq!(Matrix::determinant(&Matrix::init(2, 2, vec![0.0, 1.0, 0.0, 2.0])), 0.0);
+        let formatter = MatrixFormatter { precision: Some(2), ..MatrixFormatter::default() };
+        assert_eq!(trace.render(&formatter), "\
+初始矩阵
+┌           ┐
+│ 1.00 2.00 │
+│ 3.00 4.00 │
+└           ┘
+第 1 列选第 2 行的 3.00 作为主元
+R2 <-> R1
+┌           ┐
+│ 3.00 4.00 │
+│ 1.00 2.00 │
+└           ┘
+R2 <- R2 + (-0.33) R1
+┌           ┐
+│ 3.00 4.00 │
+│ 0.00 0.67 │
+└           ┘
+第 2 列选第 2 行的 0.67 作为主元
+");
+
+        let target = Matrix::init(3, 3, vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 2.0, 0.0, 3.0]);
+        let (inverse, trace) = Matrix::inverse_traced(&target);
+        assert_matrix_close(&inverse.unwrap(), &Matrix::inverse(&target), 1e-12);
+        let latex = trace.render(&MatrixFormatter { style: OutputStyle::Latex, ..MatrixFormatter::default() });
+        assert!(latex.starts_with("\\begin{align*}\n& \\left[\\begin{array}{ccc|ccc} 0 & 2 & 1 & 1 & 0 & 0 \\\\ "));
+        assert!(latex.contains("\\xrightarrow{R_{3} \\leftrightarrow R_{1}}"));
+        assert!(latex.contains("\\text{第 1 列选第 3 行的 2 作为主元}"));
+        let (inverse, trace) = Matrix::inverse_traced(&Matrix::init(2, 2, vec![1.0, 2.0, 2.0, 4.0]));
+        assert!(inverse.is_none());
+        assert!(matches!(trace.get_steps().last(), Some(TraceStep::Operation { .. })));
+    }
+
+    #[test]
+    fn test_permutation() {
+        let p = Permutation::from_vec(vec![2, 0, 1, 4, 3]).unwrap();
+        let q = Permutation::from_vec(vec![1, 0, 2, 3, 4]).unwrap();
+        assert_eq!(Permutation::from_vec(vec![0, 0, 1]), None);
+        assert_eq!(p.cycles(), vec![vec![0, 2, 1], vec![3, 4]]);
+        assert_eq!(p.to_string(), "(1 3 2)(4 5)");
+        assert_eq!((p.sign(), q.sign(), Permutation::identity(3).sign()), (-1, -1, 1));
+        assert_eq!(&p * &p.inverse(), Permutation::identity(5));
+        assert_eq!((&p * &q).to_matrix::<i64>(), &p.to_matrix::<i64>() * &q.to_matrix::<i64>());
+        assert_eq!(Matrix::determinant(&p.to_matrix::<f64>()), p.sign() as f64);
+
+        let matrix = Matrix::init(5, 2, (0..10).collect());
+        assert_eq!(p.apply_to_rows(&matrix), &p.to_matrix() * &matrix);
+        let wide = Matrix::transpose(&matrix);
+        assert_eq!(p.apply_to_cols(&wide), &wide * &Matrix::transpose(&p.to_matrix()));
+    }
+
+    #[test]
+    fn test_lu_and_qr() {
+        let target = Matrix::init(3, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]);
+        let lu = Matrix::lu(&target);
+        assert_matrix_close(&(&lu.l * &lu.u), &lu.permutation.apply_to_rows(&target), 1e-12);
+        assert_eq!(lu.permutation.get_indices(), &vec![2, 0, 1]);
+        assert!((lu.determinant() - Matrix::determinant(&target)).abs() < 1e-12);
+        let rhs = Matrix::init(3, 1, vec![1.0, 2.0, 3.0]);
+        assert_matrix_close(&lu.solve(&rhs).unwrap(), &Matrix::solve(&target, &rhs).unwrap(), 1e-12);
+        assert!(Matrix::lu(&Matrix::init(2, 2, vec![1.0, 2.0, 2.0, 4.0])).solve(&Matrix::identity(2)).is_none());
+
+        let target = Matrix::init(4, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0, 3.0, 1.0, 2.0]);
+        let qr = Matrix::qr(&target);
+        assert_matrix_close(&(&qr.q * &qr.r), &qr.permutation.apply_to_cols(&target), 1e-12);
+        assert_matrix_close(&(&Matrix::transpose(&qr.q) * &qr.q), &Matrix::identity(4), 1e-12);
+        for row in 1..4 {
+            for col in 0..row.min(3) {
+                assert_eq!(*qr.r.get(row, col), 0.0);
+            }
+        }
+        assert_eq!(qr.rank(1e-10), 3);
+        let singular = Matrix::init(3, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 1.0, 1.0]);
+        assert_eq!(Matrix::qr(&singular).rank(1e-10), 2);
+        assert!(Matrix::qr(&singular).least_squares(&Matrix::init(3, 1, vec![1.0, 2.0, 3.0]), 1e-10).is_none());
+        // 超定方程组的最小二乘解满足法方程 AᵀA x = Aᵀb
+        let tall = Matrix::init(4, 2, vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+        let rhs = Matrix::init(4, 1, vec![1.0, 2.0, 2.0, 4.0]);
+        let solution = Matrix::qr(&tall).least_squares(&rhs, 1e-10).unwrap();
+        let normal = &Matrix::transpose(&tall) * &tall;
+        assert_matrix_close(&(&normal * &solution), &(&Matrix::transpose(&tall) * &rhs), 1e-12);
+    }
+
+    #[test]
+    fn test_smatrix() {
+        let rotation: SMatrix<f64, 2, 2> = SMatrix::from_rows([[0.0, -1.0], [1.0, 0.0]]);
+        let point: SMatrix<f64, 2, 1> = SMatrix::from_rows([[2.0], [3.0]]);
+        assert_eq!(rotation * point, SMatrix::from_rows([[-3.0], [2.0]]));
+        assert_eq!(rotation.determinant(), 1.0);
+        assert_eq!(rotation * rotation.inverse().unwrap(), SMatrix::identity());
+        assert_eq!(SMatrix::<f64, 2, 2>::from_rows([[1.0, 2.0], [2.0, 4.0]]).inverse(), None);
+
+        let transform: SMatrix<f64, 3, 3> = SMatrix::from_rows([[2.0, 0.0, 1.0], [1.0, 3.0, 2.0], [1.0, 1.0, 2.0]]);
+        let dynamic: Matrix<f64> = Matrix::from(transform);
+        assert_eq!(transform.determinant(), Matrix::determinant(&dynamic));
+        let inverse: Matrix<f64> = Matrix::from(transform.inverse().unwrap());
+        assert_matrix_close(&inverse, &Matrix::inverse(&dynamic), 1e-12);
+        assert_eq!(SMatrix::<f64, 3, 3>::try_from(&dynamic), Ok(transform));
+        assert_eq!(SMatrix::<f64, 2, 3>::try_from(&dynamic), Err(SizeMismatch { expected: (2, 3), found: (3, 3) }));
+
+        let wide: SMatrix<i64, 2, 3> = SMatrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+        let product: SMatrix<i64, 2, 2> = wide * wide.transpose();
+        assert_eq!(product, SMatrix::from_rows([[14, 32], [32, 77]]));
+        assert_eq!((product - product * 2 + product)[(1, 0)], 0);
+        assert_eq!(format!("{}", SMatrix::<i64, 2, 2>::identity()), format!("{}", Matrix::<i64>::identity(2)));
+    }
+
+    #[test]
+    fn test_eigenvalues() {
+        let mut values = Matrix::eigenvalues(&Matrix::from_str("[2 0 0; 1 3 0; 4 5 6]").unwrap()).unwrap();
+        values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        for (value, expected) in values.iter().zip([2.0, 3.0, 6.0]) {
+            assert!((value.0 - expected).abs() < 1e-10 && value.1.abs() < 1e-10, "{:?}", value);
+        }
+        let rotation = Matrix::eigenvalues(&Matrix::from_str("[0 -1; 1 0]").unwrap()).unwrap();
+        assert!(rotation.iter().all(|(real, imaginary)| real.abs() < 1e-12 && (imaginary.abs() - 1.0).abs() < 1e-12));
+        // 特征值之和等于迹，之积等于行列式
+        #[cfg(feature = "rand")]
+        {
+            let matrix = crate::matrix::random_f64_matrix(7, 7, 3, -1.0, 1.0);
+            let values = Matrix::eigenvalues(&matrix).unwrap();
+            let trace: f64 = (0..7).map(|i| *matrix.get(i, i)).sum();
+            assert!((values.iter().map(|v| v.0).sum::<f64>() - trace).abs() < 1e-9);
+            assert!(values.iter().map(|v| v.1).sum::<f64>().abs() < 1e-9);
+            let product = values.iter().fold((1.0, 0.0), |(a, b), (c, d)| (a * c - b * d, a * d + b * c));
+            assert!((product.0 - Matrix::determinant(&matrix)).abs() < 1e-9 && product.1.abs() < 1e-9);
+        }
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_cli() {
+        let run = |args: &[&str], input: &str| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            let mut output: Vec<u8> = Vec::new();
+            let result = cli::run(&args, &mut input.as_bytes(), &mut output);
+            (result.map_err(|error| error.exit_code()), String::from_utf8(output).unwrap())
+        };
+        assert_eq!(run(&["det"], "[1 2; 3 4]"), (Ok(()), "-2\n".to_string()));
+        // 首个主元为负数，过去按带符号的值选主元会把 0 当作主元而输出 NaN
+        assert_eq!(run(&["det"], "[-1 2; 0 3]"), (Ok(()), "-3\n".to_string()));
+        assert_eq!(run(&["det"], "[1 2; 2 4]"), (Ok(()), "0\n".to_string()));
+        assert_eq!(run(&["inv", "--output", "literal"], "1,0\n0,4\n"), (Ok(()), "[1 0; 0 0.25]\n".to_string()));
+        assert_eq!(run(&["rref", "--output", "csv"], "%%MatrixMarket matrix array real general\n2 2\n1\n2\n2\n4\n"),
+            (Ok(()), "1,2\n0,0\n".to_string()));
+        assert_eq!(run(&["det", "--delimiter", ";", "--output", "literal"], "2;0\n0;3\n"), (Ok(()), "6\n".to_string()));
+        #[cfg(feature = "rand")]
+        assert_eq!(run(&["random", "2", "2", "--integer", "--seed", "1"], "").0, Ok(()));
+        assert_eq!(run(&["inv"], "[1 2; 2 4]").0, Err(cli::EXIT_SINGULAR));
+        assert_eq!(run(&["inv", "--trace"], "[1 2; 2 4]").0, Err(cli::EXIT_SINGULAR));
+        let (result, output) = run(&["det", "--trace"], "[1 2; 3 4]");
+        assert!(result.is_ok() && output.starts_with("初始矩阵\n") && output.ends_with("第 2 列选第 2 行的 0.6666666666666667 作为主元\n-2\n"));
+        assert_eq!(run(&["det"], "[1 2 3; 4 5 6]").0, Err(cli::EXIT_DIMENSION));
+        let error = cli::run(&["det".to_string()], &mut "[1 2 3; 4 5 6]".as_bytes(), &mut Vec::new()).err().unwrap();
+        assert_eq!(error.to_string(), "det 需要方阵，实际为 2x3");
+        for help in ["--help", "-h"] {
+            let (result, output) = run(&[help], "");
+            assert!(result.is_ok() && output.contains("random <行数> <列数>"));
+            assert_eq!(run(&["det", help], "").0, Ok(()));
+        }
+        assert_eq!(run(&["det"], "[1 2; 3 a]").0, Err(cli::EXIT_PARSE));
+        assert_eq!(run(&["det"], "%%MatrixMarket matrix coordinate real symmetric\n2 3 1\n1 3 5\n").0, Err(cli::EXIT_PARSE));
+        assert_eq!(run(&["roots"], "x^2 +").0, Err(cli::EXIT_PARSE));
+        assert_eq!(run(&["roots"], &format!("x^3 - 0.{}1", "0".repeat(399))).0, Ok(()));
+        let (result, output) = run(&["roots", "--output", "csv"], "x² = 4");
+        assert!(result.is_ok());
+        let mut roots: Vec<Vec<f64>> = output.lines()
+            .map(|line| line.split(',').map(|value| value.parse().unwrap()).collect())
+            .collect();
+        roots.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+        assert!((roots[0][0] + 2.0).abs() < 1e-6 && (roots[1][0] - 2.0).abs() < 1e-6);
+        assert!(roots.iter().all(|root| root[1].abs() < 1e-6));
+        assert_eq!(run(&["mul", "-", "-"], "").0, Err(cli::EXIT_USAGE));
+        assert_eq!(run(&["transpose"], "").0, Err(cli::EXIT_USAGE));
+        assert_eq!(run(&["det", "/nonexistent/matrix.csv"], "").0, Err(cli::EXIT_IO));
+    }
+
 }
 
 // 确保将测试放在正确的模块内，并且对于那些依赖具体实现的测试，你需要根据你的方法实现来具体编写测试用例。